@@ -0,0 +1,141 @@
+use anchor_lang::{AccountDeserialize, Discriminator, Result};
+
+pub use bs_bet::state::{
+    Achievement, AchievementClaim, ActiveBet, Challenge, Market, ParlayBet, ProgramConfig,
+    RecurringBetPlan, ReferralStats, Tournament, TournamentEntry, UserAuthState, UserProfile,
+};
+
+/// Decodes raw account data (discriminator included) as `T`, checking the discriminator.
+pub fn decode<T: AccountDeserialize>(data: &[u8]) -> Result<T> {
+    let mut data = data;
+    T::try_deserialize(&mut data)
+}
+
+/// Any `bs_bet` account, for callers scanning program accounts without knowing their type.
+#[derive(Debug)]
+pub enum BsBetAccount {
+    UserAuthState(UserAuthState),
+    UserProfile(UserProfile),
+    ActiveBet(ActiveBet),
+    ProgramConfig(ProgramConfig),
+    ReferralStats(ReferralStats),
+    RecurringBetPlan(RecurringBetPlan),
+    ParlayBet(ParlayBet),
+    Challenge(Challenge),
+    Tournament(Tournament),
+    TournamentEntry(TournamentEntry),
+    Achievement(Achievement),
+    AchievementClaim(AchievementClaim),
+    Market(Market),
+}
+
+/// Decodes account data by its discriminator. Returns `None` for data that is not a
+/// `bs_bet` account.
+pub fn decode_any(data: &[u8]) -> Option<Result<BsBetAccount>> {
+    fn is<T: Discriminator>(data: &[u8]) -> bool {
+        data.starts_with(T::DISCRIMINATOR)
+    }
+
+    let account = if is::<UserAuthState>(data) {
+        decode(data).map(BsBetAccount::UserAuthState)
+    } else if is::<UserProfile>(data) {
+        decode(data).map(BsBetAccount::UserProfile)
+    } else if is::<ActiveBet>(data) {
+        decode(data).map(BsBetAccount::ActiveBet)
+    } else if is::<ProgramConfig>(data) {
+        decode(data).map(BsBetAccount::ProgramConfig)
+    } else if is::<ReferralStats>(data) {
+        decode(data).map(BsBetAccount::ReferralStats)
+    } else if is::<RecurringBetPlan>(data) {
+        decode(data).map(BsBetAccount::RecurringBetPlan)
+    } else if is::<ParlayBet>(data) {
+        decode(data).map(BsBetAccount::ParlayBet)
+    } else if is::<Challenge>(data) {
+        decode(data).map(BsBetAccount::Challenge)
+    } else if is::<Tournament>(data) {
+        decode(data).map(BsBetAccount::Tournament)
+    } else if is::<TournamentEntry>(data) {
+        decode(data).map(BsBetAccount::TournamentEntry)
+    } else if is::<Achievement>(data) {
+        decode(data).map(BsBetAccount::Achievement)
+    } else if is::<AchievementClaim>(data) {
+        decode(data).map(BsBetAccount::AchievementClaim)
+    } else if is::<Market>(data) {
+        decode(data).map(BsBetAccount::Market)
+    } else {
+        return None;
+    };
+    Some(account)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::AccountSerialize;
+
+    fn encode<T: AccountSerialize>(account: &T) -> Vec<u8> {
+        let mut data = Vec::new();
+        account.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn decode_any_round_trips_every_account_type() {
+        let data = [
+            encode(&UserAuthState::default()),
+            encode(&UserProfile::default()),
+            encode(&ActiveBet::default()),
+            encode(&ProgramConfig::default()),
+            encode(&ReferralStats::default()),
+            encode(&RecurringBetPlan::default()),
+            encode(&ParlayBet::default()),
+            encode(&Challenge::default()),
+            encode(&Tournament::default()),
+            encode(&TournamentEntry::default()),
+            encode(&Achievement::default()),
+            encode(&AchievementClaim::default()),
+            encode(&Market::default()),
+        ];
+        let decoded: Vec<_> = data.iter().map(|data| decode_any(data).unwrap().unwrap()).collect();
+        assert!(matches!(
+            decoded[..],
+            [
+                BsBetAccount::UserAuthState(_),
+                BsBetAccount::UserProfile(_),
+                BsBetAccount::ActiveBet(_),
+                BsBetAccount::ProgramConfig(_),
+                BsBetAccount::ReferralStats(_),
+                BsBetAccount::RecurringBetPlan(_),
+                BsBetAccount::ParlayBet(_),
+                BsBetAccount::Challenge(_),
+                BsBetAccount::Tournament(_),
+                BsBetAccount::TournamentEntry(_),
+                BsBetAccount::Achievement(_),
+                BsBetAccount::AchievementClaim(_),
+                BsBetAccount::Market(_),
+            ]
+        ));
+    }
+
+    #[test]
+    fn decode_any_keeps_field_values() {
+        let profile = UserProfile {
+            authority: anchor_lang::prelude::Pubkey::new_unique(),
+            points: 1_234,
+            referrer: Some(anchor_lang::prelude::Pubkey::new_unique()),
+            ..Default::default()
+        };
+        let Some(Ok(BsBetAccount::UserProfile(decoded))) = decode_any(&encode(&profile)) else {
+            panic!("not decoded as a UserProfile");
+        };
+        assert_eq!(decoded.authority, profile.authority);
+        assert_eq!(decoded.points, profile.points);
+        assert_eq!(decoded.referrer, profile.referrer);
+    }
+
+    #[test]
+    fn decode_any_ignores_foreign_data() {
+        assert!(decode_any(&[0; 64]).is_none());
+        assert!(decode_any(&[]).is_none());
+    }
+}
@@ -0,0 +1,1054 @@
+//! One builder per program instruction. Account lists come from the program's generated
+//! `accounts` structs, so a builder stops compiling if the on-chain context changes.
+//!
+//! Builders named `*_ephemeral`, the `commit_*` ones and the undelegation ones target the
+//! ephemeral rollup and carry the magic program and magic context accounts.
+
+use anchor_lang::prelude::{AccountMeta, Pubkey};
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::{system_program, sysvar};
+use anchor_lang::{InstructionData, ToAccountMetas};
+use ephemeral_rollups_sdk::consts::{DELEGATION_PROGRAM_ID, MAGIC_CONTEXT_ID, MAGIC_PROGRAM_ID};
+
+use bs_bet::accounts as ctx;
+use bs_bet::badge::{associated_token_address, ASSOCIATED_TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID};
+use bs_bet::create_delegation_message;
+use bs_bet::instruction as ix;
+use bs_bet::state::{BetTerms, DelegationArgs, ParlayLegArgs, ProgramConfigArgs, TournamentArgs};
+
+use crate::pda::*;
+
+fn instruction(accounts: impl ToAccountMetas, data: impl InstructionData) -> Instruction {
+    Instruction {
+        program_id: bs_bet::ID,
+        accounts: accounts.to_account_metas(None),
+        data: data.data(),
+    }
+}
+
+fn instruction_with_remaining(
+    accounts: impl ToAccountMetas,
+    data: impl InstructionData,
+    remaining_accounts: impl IntoIterator<Item = AccountMeta>,
+) -> Instruction {
+    let mut instruction = instruction(accounts, data);
+    instruction.accounts.extend(remaining_accounts);
+    instruction
+}
+
+// --- Profile & rewards ---
+
+/// `referrer` is only accepted when the profile is first created.
+pub fn create_user_profile(user: &Pubkey, referrer: Option<Pubkey>) -> Instruction {
+    instruction(
+        ctx::CreateUserProfile {
+            user_profile: user_profile_pda(user),
+            user_auth_state_for_profile_creation: user_auth_state_pda(user),
+            active_bet: active_bet_pda(user),
+            referral_stats: referrer.as_ref().map(referral_stats_pda),
+            user_authority: *user,
+            system_program: system_program::ID,
+        },
+        ix::CreateUserProfile { referrer },
+    )
+}
+
+pub fn claim_referral_reward(referrer: &Pubkey, referee: &Pubkey) -> Instruction {
+    instruction(
+        ctx::ClaimReferralReward {
+            referrer_signer: *referrer,
+            referrer_profile: user_profile_pda(referrer),
+            referee_profile: user_profile_pda(referee),
+            referral_stats: referral_stats_pda(referrer),
+            program_config: program_config_pda(),
+        },
+        ix::ClaimReferralReward { referee: *referee },
+    )
+}
+
+pub fn create_achievement(admin: &Pubkey, id: u16, kind: u8, threshold: u64) -> Instruction {
+    let achievement = achievement_pda(id);
+    instruction(
+        ctx::CreateAchievement {
+            admin: *admin,
+            program_config: program_config_pda(),
+            achievement,
+            badge_mint: badge_mint_pda(&achievement),
+            token_program: TOKEN_2022_PROGRAM_ID,
+            system_program: system_program::ID,
+        },
+        ix::CreateAchievement { id, kind, threshold },
+    )
+}
+
+pub fn update_achievement(admin: &Pubkey, id: u16, threshold: u64) -> Instruction {
+    instruction(
+        ctx::UpdateAchievement {
+            admin: *admin,
+            program_config: program_config_pda(),
+            achievement: achievement_pda(id),
+        },
+        ix::UpdateAchievement { threshold },
+    )
+}
+
+pub fn claim_achievement_badge(user: &Pubkey, achievement_id: u16) -> Instruction {
+    let achievement = achievement_pda(achievement_id);
+    let badge_mint = badge_mint_pda(&achievement);
+    instruction(
+        ctx::ClaimAchievementBadge {
+            user_signer: *user,
+            user_profile: user_profile_pda(user),
+            achievement,
+            achievement_claim: achievement_claim_pda(&achievement, user),
+            badge_mint,
+            user_badge_account: associated_token_address(user, &badge_mint),
+            token_program: TOKEN_2022_PROGRAM_ID,
+            associated_token_program: ASSOCIATED_TOKEN_PROGRAM_ID,
+            system_program: system_program::ID,
+        },
+        ix::ClaimAchievementBadge {},
+    )
+}
+
+pub fn transfer_points(user: &Pubkey, recipient: &Pubkey, amount: u64) -> Instruction {
+    instruction(
+        ctx::TransferPoints {
+            user_signer: *user,
+            sender_profile: user_profile_pda(user),
+            recipient_profile: user_profile_pda(recipient),
+            program_config: program_config_pda(),
+        },
+        ix::TransferPoints {
+            recipient: *recipient,
+            amount,
+        },
+    )
+}
+
+pub fn claim_daily_points(user: &Pubkey) -> Instruction {
+    instruction(
+        ctx::ClaimDailyPoints {
+            user_signer: *user,
+            user_profile: user_profile_pda(user),
+            program_config: program_config_pda(),
+        },
+        ix::ClaimDailyPoints {},
+    )
+}
+
+/// Resizes a profile created before the current `UserProfile` layout.
+pub fn migrate_user_profile(user: &Pubkey) -> Instruction {
+    instruction(
+        ctx::MigrateUserProfile {
+            user_signer: *user,
+            user_profile: user_profile_pda(user),
+            system_program: system_program::ID,
+        },
+        ix::MigrateUserProfile {},
+    )
+}
+
+/// Resizes an auth state created before the current `UserAuthState` layout.
+pub fn migrate_user_auth_state(user: &Pubkey) -> Instruction {
+    instruction(
+        ctx::MigrateUserAuthState {
+            user_signer: *user,
+            user_auth_state: user_auth_state_pda(user),
+            system_program: system_program::ID,
+        },
+        ix::MigrateUserAuthState {},
+    )
+}
+
+// --- Program config ---
+
+pub fn initialize_program_config(admin: &Pubkey, args: ProgramConfigArgs) -> Instruction {
+    instruction(
+        ctx::InitializeProgramConfig {
+            program_config: program_config_pda(),
+            admin: *admin,
+            program: bs_bet::ID,
+            program_data: program_data_address(),
+            system_program: system_program::ID,
+        },
+        ix::InitializeProgramConfig { args },
+    )
+}
+
+pub fn update_program_config(admin: &Pubkey, args: ProgramConfigArgs) -> Instruction {
+    instruction(
+        ctx::UpdateProgramConfig {
+            program_config: program_config_pda(),
+            admin: *admin,
+        },
+        ix::UpdateProgramConfig { args },
+    )
+}
+
+// --- Delegation ---
+//
+// `nonce` is the current `UserAuthState::nonce`; the signed message is rebuilt with
+// `create_delegation_message` so it always matches what the program expects.
+
+pub fn manage_delegation(
+    user: &Pubkey,
+    delegation_action: u8,
+    nonce: u64,
+    signature: [u8; 64],
+) -> Instruction {
+    instruction(
+        ctx::ManageDelegation {
+            user_auth_state: user_auth_state_pda(user),
+            user_authority: *user,
+            system_program: system_program::ID,
+            ix_sysvar: sysvar::instructions::ID,
+        },
+        ix::ManageDelegation {
+            delegation_action,
+            user_signed_message: create_delegation_message(user, nonce).into_bytes(),
+            _signature: signature,
+        },
+    )
+}
+
+pub fn recover_delegation_state(user: &Pubkey) -> Instruction {
+    instruction(
+        ctx::RecoverDelegationState {
+            user_authority: *user,
+            user_auth_state: user_auth_state_pda(user),
+            user_profile: user_profile_pda(user),
+            active_bet: active_bet_pda(user),
+        },
+        ix::RecoverDelegationState {},
+    )
+}
+
+pub fn set_delegation_preferences(user: &Pubkey, delegation_args: DelegationArgs) -> Instruction {
+    instruction(
+        ctx::SetDelegationPreferences {
+            user_authority: *user,
+            user_auth_state: user_auth_state_pda(user),
+        },
+        ix::SetDelegationPreferences { delegation_args },
+    )
+}
+
+/// `use_program_config` as for [`delegate_auth_state`].
+pub fn enable_quick_bets(
+    user: &Pubkey,
+    nonce: u64,
+    signature: [u8; 64],
+    delegation_args: DelegationArgs,
+    use_program_config: bool,
+) -> Instruction {
+    let auth_state = user_auth_state_pda(user);
+    let user_profile = user_profile_pda(user);
+    let active_bet = active_bet_pda(user);
+    let auth_state_delegation = delegation_accounts(&auth_state);
+    let user_profile_delegation = delegation_accounts(&user_profile);
+    let active_bet_delegation = delegation_accounts(&active_bet);
+    instruction(
+        ctx::EnableQuickBets {
+            payer: *user,
+            auth_state,
+            buffer_auth_state: auth_state_delegation.buffer,
+            delegation_record_auth_state: auth_state_delegation.delegation_record,
+            delegation_metadata_auth_state: auth_state_delegation.delegation_metadata,
+            user_profile,
+            buffer_user_profile: user_profile_delegation.buffer,
+            delegation_record_user_profile: user_profile_delegation.delegation_record,
+            delegation_metadata_user_profile: user_profile_delegation.delegation_metadata,
+            active_bet,
+            buffer_active_bet: active_bet_delegation.buffer,
+            delegation_record_active_bet: active_bet_delegation.delegation_record,
+            delegation_metadata_active_bet: active_bet_delegation.delegation_metadata,
+            ix_sysvar: sysvar::instructions::ID,
+            program_config: use_program_config.then(program_config_pda),
+            owner_program: bs_bet::ID,
+            delegation_program: DELEGATION_PROGRAM_ID,
+            system_program: system_program::ID,
+        },
+        ix::EnableQuickBets {
+            user_signed_message: create_delegation_message(user, nonce).into_bytes(),
+            _signature: signature,
+            delegation_args,
+        },
+    )
+}
+
+pub fn disable_quick_bets(user: &Pubkey, price_feed: Option<Pubkey>) -> Instruction {
+    instruction(
+        ctx::DisableQuickBets {
+            user_authority: *user,
+            user_auth_state: user_auth_state_pda(user),
+            user_profile: user_profile_pda(user),
+            active_bet: active_bet_pda(user),
+            pyth_price_feed: price_feed,
+            magic_program: MAGIC_PROGRAM_ID,
+            magic_context: MAGIC_CONTEXT_ID,
+        },
+        ix::DisableQuickBets {},
+    )
+}
+
+/// `use_program_config` passes the `ProgramConfig` PDA; leave it out on deployments without one.
+pub fn delegate_auth_state(user: &Pubkey, use_program_config: bool) -> Instruction {
+    let pda = user_auth_state_pda(user);
+    let delegation = delegation_accounts(&pda);
+    instruction(
+        ctx::DelegateAuthState {
+            payer: *user,
+            pda,
+            buffer_pda: delegation.buffer,
+            delegation_record_pda: delegation.delegation_record,
+            delegation_metadata_pda: delegation.delegation_metadata,
+            program_config: use_program_config.then(program_config_pda),
+            owner_program: bs_bet::ID,
+            delegation_program: DELEGATION_PROGRAM_ID,
+            system_program: system_program::ID,
+        },
+        ix::DelegateAuthState {},
+    )
+}
+
+/// See [`delegate_auth_state`].
+pub fn delegate_user_profile(user: &Pubkey, use_program_config: bool) -> Instruction {
+    let pda = user_profile_pda(user);
+    let delegation = delegation_accounts(&pda);
+    instruction(
+        ctx::DelegateUserProfile {
+            payer: *user,
+            pda,
+            buffer_pda: delegation.buffer,
+            delegation_record_pda: delegation.delegation_record,
+            delegation_metadata_pda: delegation.delegation_metadata,
+            user_auth_state: user_auth_state_pda(user),
+            program_config: use_program_config.then(program_config_pda),
+            owner_program: bs_bet::ID,
+            delegation_program: DELEGATION_PROGRAM_ID,
+            system_program: system_program::ID,
+        },
+        ix::DelegateUserProfile {},
+    )
+}
+
+/// See [`delegate_auth_state`].
+pub fn delegate_active_bet(user: &Pubkey, use_program_config: bool) -> Instruction {
+    let pda = active_bet_pda(user);
+    let delegation = delegation_accounts(&pda);
+    instruction(
+        ctx::DelegateActiveBet {
+            payer: *user,
+            pda,
+            buffer_pda: delegation.buffer,
+            delegation_record_pda: delegation.delegation_record,
+            delegation_metadata_pda: delegation.delegation_metadata,
+            user_auth_state: user_auth_state_pda(user),
+            program_config: use_program_config.then(program_config_pda),
+            owner_program: bs_bet::ID,
+            delegation_program: DELEGATION_PROGRAM_ID,
+            system_program: system_program::ID,
+        },
+        ix::DelegateActiveBet {},
+    )
+}
+
+// --- Single bets ---
+
+/// `record_market_stats` passes the asset's market PDA so the bet counts towards its round
+/// stats. The stats are best-effort: other clients may leave the market out.
+#[allow(clippy::too_many_arguments)]
+pub fn open_bet_normal(
+    user: &Pubkey,
+    asset_name: &str,
+    direction: u8,
+    amount: u64,
+    duration_seconds: i64,
+    bet_terms: BetTerms,
+    price_feed: &Pubkey,
+    record_market_stats: bool,
+) -> Instruction {
+    instruction(
+        ctx::OpenBetNormal {
+            user_signer: *user,
+            user_auth_state: user_auth_state_pda(user),
+            user_profile: user_profile_pda(user),
+            active_bet: active_bet_pda(user),
+            pyth_price_feed: *price_feed,
+            market_stats: record_market_stats.then(|| market_pda(asset_name)),
+            system_program: system_program::ID,
+        },
+        ix::OpenBetNormal {
+            asset_name_arg: asset_name.to_string(),
+            direction_arg: direction,
+            amount_arg: amount,
+            duration_seconds_arg: duration_seconds,
+            bet_terms_arg: bet_terms,
+        },
+    )
+}
+
+/// Sent to the ephemeral rollup and signed by `user`, who owns the PDAs.
+#[allow(clippy::too_many_arguments)]
+pub fn open_bet_ephemeral(
+    user: &Pubkey,
+    asset_name: &str,
+    direction: u8,
+    amount: u64,
+    duration_seconds: i64,
+    bet_terms: BetTerms,
+    price_feed: &Pubkey,
+    record_market_stats: bool,
+) -> Instruction {
+    instruction(
+        ctx::OpenBetEphemeral {
+            payer: *user,
+            user_auth_state: user_auth_state_pda(user),
+            user_profile: user_profile_pda(user),
+            active_bet: active_bet_pda(user),
+            pyth_price_feed: *price_feed,
+            market_stats: record_market_stats.then(|| market_pda(asset_name)),
+            magic_program: MAGIC_PROGRAM_ID,
+            magic_context: MAGIC_CONTEXT_ID,
+        },
+        ix::OpenBetEphemeral {
+            asset_name_arg: asset_name.to_string(),
+            direction_arg: direction,
+            amount_arg: amount,
+            duration_seconds_arg: duration_seconds,
+            bet_terms_arg: bet_terms,
+            user_authority_for_pdas: *user,
+        },
+    )
+}
+
+pub fn record_price_sample(keeper: &Pubkey, user: &Pubkey, price_feed: &Pubkey) -> Instruction {
+    instruction(
+        ctx::RecordPriceSample {
+            keeper: *keeper,
+            active_bet: active_bet_pda(user),
+            user_profile: user_profile_pda(user),
+            pyth_price_feed: *price_feed,
+        },
+        ix::RecordPriceSample {
+            user_authority_for_pdas: *user,
+        },
+    )
+}
+
+/// `price_feed` opens the new bet; `settlement_price_feed` settles the prior one and is
+/// required while it is still live.
+#[allow(clippy::too_many_arguments)]
+pub fn settle_and_reopen_bet(
+    user: &Pubkey,
+    asset_name: &str,
+    direction: u8,
+    amount: u64,
+    duration_seconds: i64,
+    bet_terms: BetTerms,
+    price_feed: &Pubkey,
+    settlement_price_feed: Option<Pubkey>,
+) -> Instruction {
+    instruction(
+        ctx::SettleAndReopenBet {
+            user_signer: *user,
+            user_auth_state: user_auth_state_pda(user),
+            user_profile: user_profile_pda(user),
+            active_bet: active_bet_pda(user),
+            pyth_price_feed: *price_feed,
+            settlement_price_feed,
+        },
+        ix::SettleAndReopenBet {
+            asset_name_arg: asset_name.to_string(),
+            direction_arg: direction,
+            amount_arg: amount,
+            duration_seconds_arg: duration_seconds,
+            bet_terms_arg: bet_terms,
+        },
+    )
+}
+
+/// `price_feed` must be the asset's Pyth push feed, see [`crate::pda::push_feed_pda`].
+pub fn cancel_bet(user: &Pubkey, price_feed: &Pubkey) -> Instruction {
+    instruction(
+        ctx::CancelBet {
+            user_signer: *user,
+            user_auth_state: user_auth_state_pda(user),
+            user_profile: user_profile_pda(user),
+            active_bet: active_bet_pda(user),
+            program_config: program_config_pda(),
+            pyth_price_feed: *price_feed,
+        },
+        ix::CancelBet {},
+    )
+}
+
+/// `price_feed` must be the asset's Pyth push feed, see [`crate::pda::push_feed_pda`].
+pub fn cash_out_bet(user: &Pubkey, price_feed: &Pubkey) -> Instruction {
+    instruction(
+        ctx::CashOutBet {
+            user_signer: *user,
+            user_auth_state: user_auth_state_pda(user),
+            user_profile: user_profile_pda(user),
+            active_bet: active_bet_pda(user),
+            pyth_price_feed: *price_feed,
+        },
+        ix::CashOutBet {},
+    )
+}
+
+pub fn migrate_active_bet(user: &Pubkey) -> Instruction {
+    instruction(
+        ctx::MigrateActiveBet {
+            user_signer: *user,
+            active_bet: active_bet_pda(user),
+            system_program: system_program::ID,
+        },
+        ix::MigrateActiveBet {},
+    )
+}
+
+/// Permissionless: any `payer` may resolve `user`'s bet. `price_feed` must hold a print
+/// published within `SETTLEMENT_WINDOW_SECONDS` after expiry; pass `None` to void a bet
+/// still unsettled `PRICE_OUTAGE_VOID_SECONDS` after expiry.
+pub fn resolve_bet_normal(payer: &Pubkey, user: &Pubkey, price_feed: Option<Pubkey>) -> Instruction {
+    instruction(
+        ctx::ResolveBetNormal {
+            payer: *payer,
+            user_auth_state: user_auth_state_pda(user),
+            user_profile: user_profile_pda(user),
+            active_bet: active_bet_pda(user),
+            pyth_price_feed: price_feed,
+            clock: sysvar::clock::ID,
+        },
+        ix::ResolveBetNormal {
+            _user_authority_for_pdas: *user,
+        },
+    )
+}
+
+/// Quick Bets counterpart of [`cancel_bet`]; only `user` may sign.
+pub fn cancel_bet_ephemeral(user: &Pubkey, price_feed: &Pubkey) -> Instruction {
+    instruction(
+        ctx::CancelBetEphemeral {
+            payer: *user,
+            user_auth_state: user_auth_state_pda(user),
+            user_profile: user_profile_pda(user),
+            active_bet: active_bet_pda(user),
+            program_config: program_config_pda(),
+            pyth_price_feed: *price_feed,
+            magic_program: MAGIC_PROGRAM_ID,
+            magic_context: MAGIC_CONTEXT_ID,
+        },
+        ix::CancelBetEphemeral {
+            user_authority_for_pdas: *user,
+        },
+    )
+}
+
+/// Permissionless: any `payer` (typically the resolution crank) may resolve `user`'s bet.
+/// `price_feed` as for [`resolve_bet_normal`].
+pub fn resolve_bet_ephemeral(payer: &Pubkey, user: &Pubkey, price_feed: Option<Pubkey>) -> Instruction {
+    instruction(
+        ctx::ResolveBetEphemeral {
+            payer: *payer,
+            user_auth_state: user_auth_state_pda(user),
+            user_profile: user_profile_pda(user),
+            active_bet: active_bet_pda(user),
+            pyth_price_feed: price_feed,
+            clock: sysvar::clock::ID,
+            magic_program: MAGIC_PROGRAM_ID,
+            magic_context: MAGIC_CONTEXT_ID,
+        },
+        ix::ResolveBetEphemeral {
+            user_authority_for_pdas: *user,
+        },
+    )
+}
+
+// --- Recurring plans ---
+
+#[allow(clippy::too_many_arguments)]
+pub fn create_recurring_plan(
+    user: &Pubkey,
+    asset_name: &str,
+    direction: u8,
+    amount: u64,
+    duration_seconds: i64,
+    max_iterations: u32,
+    stop_loss_points: u64,
+) -> Instruction {
+    instruction(
+        ctx::CreateRecurringPlan {
+            user_signer: *user,
+            recurring_plan: recurring_plan_pda(user),
+            system_program: system_program::ID,
+        },
+        ix::CreateRecurringPlan {
+            asset_name_arg: asset_name.to_string(),
+            direction_arg: direction,
+            amount_arg: amount,
+            duration_seconds_arg: duration_seconds,
+            max_iterations_arg: max_iterations,
+            stop_loss_points_arg: stop_loss_points,
+        },
+    )
+}
+
+pub fn set_recurring_plan_paused(user: &Pubkey, paused: bool) -> Instruction {
+    instruction(
+        ctx::ManageRecurringPlan {
+            user_signer: *user,
+            recurring_plan: recurring_plan_pda(user),
+        },
+        ix::SetRecurringPlanPaused { paused },
+    )
+}
+
+pub fn cancel_recurring_plan(user: &Pubkey) -> Instruction {
+    instruction(
+        ctx::CancelRecurringPlan {
+            user_signer: *user,
+            recurring_plan: recurring_plan_pda(user),
+        },
+        ix::CancelRecurringPlan {},
+    )
+}
+
+pub fn execute_recurring_plan(keeper: &Pubkey, user: &Pubkey, price_feed: &Pubkey) -> Instruction {
+    instruction(
+        ctx::ExecuteRecurringPlan {
+            keeper: *keeper,
+            recurring_plan: recurring_plan_pda(user),
+            user_profile: user_profile_pda(user),
+            active_bet: active_bet_pda(user),
+            pyth_price_feed: *price_feed,
+        },
+        ix::ExecuteRecurringPlan {
+            user_authority_for_pdas: *user,
+        },
+    )
+}
+
+// --- Parlays ---
+//
+// `price_feeds` holds one price update per leg, in leg order.
+
+pub fn open_parlay_bet(
+    user: &Pubkey,
+    legs: Vec<ParlayLegArgs>,
+    amount: u64,
+    duration_seconds: i64,
+    price_feeds: &[Pubkey],
+) -> Instruction {
+    instruction_with_remaining(
+        ctx::OpenParlayBet {
+            user_signer: *user,
+            user_profile: user_profile_pda(user),
+            parlay_bet: parlay_bet_pda(user),
+            system_program: system_program::ID,
+        },
+        ix::OpenParlayBet {
+            legs_arg: legs,
+            amount_arg: amount,
+            duration_seconds_arg: duration_seconds,
+        },
+        price_feeds
+            .iter()
+            .map(|feed| AccountMeta::new_readonly(*feed, false)),
+    )
+}
+
+pub fn resolve_parlay_bet(user: &Pubkey, price_feeds: &[Pubkey]) -> Instruction {
+    instruction_with_remaining(
+        ctx::ResolveParlayBet {
+            user_signer: *user,
+            user_profile: user_profile_pda(user),
+            parlay_bet: parlay_bet_pda(user),
+        },
+        ix::ResolveParlayBet {},
+        price_feeds
+            .iter()
+            .map(|feed| AccountMeta::new_readonly(*feed, false)),
+    )
+}
+
+// --- Challenges ---
+
+#[allow(clippy::too_many_arguments)]
+pub fn create_challenge(
+    creator: &Pubkey,
+    challenge_id: u64,
+    asset_name: &str,
+    direction: u8,
+    stake: u64,
+    duration_seconds: i64,
+    opponent: Option<Pubkey>,
+) -> Instruction {
+    instruction(
+        ctx::CreateChallenge {
+            creator: *creator,
+            creator_profile: user_profile_pda(creator),
+            challenge: challenge_pda(creator, challenge_id),
+            system_program: system_program::ID,
+        },
+        ix::CreateChallenge {
+            challenge_id,
+            asset_name_arg: asset_name.to_string(),
+            direction_arg: direction,
+            stake_arg: stake,
+            duration_seconds_arg: duration_seconds,
+            opponent_arg: opponent,
+        },
+    )
+}
+
+pub fn accept_challenge(
+    acceptor: &Pubkey,
+    creator: &Pubkey,
+    challenge_id: u64,
+    price_feed: &Pubkey,
+) -> Instruction {
+    instruction(
+        ctx::AcceptChallenge {
+            acceptor: *acceptor,
+            acceptor_profile: user_profile_pda(acceptor),
+            challenge: challenge_pda(creator, challenge_id),
+            pyth_price_feed: *price_feed,
+        },
+        ix::AcceptChallenge {},
+    )
+}
+
+pub fn cancel_challenge(creator: &Pubkey, challenge_id: u64) -> Instruction {
+    instruction(
+        ctx::CancelChallenge {
+            creator: *creator,
+            creator_profile: user_profile_pda(creator),
+            challenge: challenge_pda(creator, challenge_id),
+        },
+        ix::CancelChallenge {},
+    )
+}
+
+/// `price_feed` must hold a print published within `SETTLEMENT_WINDOW_SECONDS` after the
+/// challenge's expiry.
+pub fn settle_challenge(
+    settler: &Pubkey,
+    creator: &Pubkey,
+    acceptor: &Pubkey,
+    challenge_id: u64,
+    price_feed: &Pubkey,
+) -> Instruction {
+    instruction(
+        ctx::SettleChallenge {
+            settler: *settler,
+            creator: *creator,
+            challenge: challenge_pda(creator, challenge_id),
+            creator_profile: user_profile_pda(creator),
+            acceptor_profile: user_profile_pda(acceptor),
+            pyth_price_feed: *price_feed,
+        },
+        ix::SettleChallenge {},
+    )
+}
+
+// --- Tournaments ---
+
+pub fn create_tournament(admin: &Pubkey, tournament_id: u64, args: TournamentArgs) -> Instruction {
+    instruction(
+        ctx::CreateTournament {
+            admin: *admin,
+            program_config: program_config_pda(),
+            tournament: tournament_pda(tournament_id),
+            system_program: system_program::ID,
+        },
+        ix::CreateTournament {
+            tournament_id,
+            args,
+        },
+    )
+}
+
+pub fn join_tournament(user: &Pubkey, tournament_id: u64) -> Instruction {
+    let tournament = tournament_pda(tournament_id);
+    instruction(
+        ctx::JoinTournament {
+            user_signer: *user,
+            user_profile: user_profile_pda(user),
+            tournament,
+            tournament_entry: tournament_entry_pda(&tournament, user),
+            system_program: system_program::ID,
+        },
+        ix::JoinTournament {},
+    )
+}
+
+pub fn open_tournament_bet(
+    user: &Pubkey,
+    tournament_id: u64,
+    direction: u8,
+    amount: u64,
+    duration_seconds: i64,
+    price_feed: &Pubkey,
+) -> Instruction {
+    let tournament = tournament_pda(tournament_id);
+    instruction(
+        ctx::OpenTournamentBet {
+            user_signer: *user,
+            tournament,
+            tournament_entry: tournament_entry_pda(&tournament, user),
+            pyth_price_feed: *price_feed,
+        },
+        ix::OpenTournamentBet {
+            direction_arg: direction,
+            amount_arg: amount,
+            duration_seconds_arg: duration_seconds,
+        },
+    )
+}
+
+/// `price_feed` as for [`resolve_bet_normal`]: `None` only voids a bet left unsettled
+/// past `PRICE_OUTAGE_VOID_SECONDS`.
+pub fn resolve_tournament_bet(
+    resolver: &Pubkey,
+    tournament_id: u64,
+    user: &Pubkey,
+    price_feed: Option<Pubkey>,
+) -> Instruction {
+    instruction(
+        ctx::ResolveTournamentBet {
+            resolver: *resolver,
+            tournament_entry: tournament_entry_pda(&tournament_pda(tournament_id), user),
+            pyth_price_feed: price_feed,
+        },
+        ix::ResolveTournamentBet {},
+    )
+}
+
+/// `entrants` must list every participant; their entries are passed as writable
+/// remaining accounts.
+pub fn settle_tournament(settler: &Pubkey, tournament_id: u64, entrants: &[Pubkey]) -> Instruction {
+    let tournament = tournament_pda(tournament_id);
+    instruction_with_remaining(
+        ctx::SettleTournament {
+            settler: *settler,
+            tournament,
+        },
+        ix::SettleTournament {},
+        entrants
+            .iter()
+            .map(|user| AccountMeta::new(tournament_entry_pda(&tournament, user), false)),
+    )
+}
+
+pub fn claim_tournament_prize(user: &Pubkey, tournament_id: u64) -> Instruction {
+    let tournament = tournament_pda(tournament_id);
+    instruction(
+        ctx::ClaimTournamentPrize {
+            user_signer: *user,
+            user_profile: user_profile_pda(user),
+            tournament,
+            tournament_entry: tournament_entry_pda(&tournament, user),
+        },
+        ix::ClaimTournamentPrize {},
+    )
+}
+
+// --- Markets ---
+
+pub fn initialize_market(admin: &Pubkey, asset_name: &str) -> Instruction {
+    instruction(
+        ctx::InitializeMarket {
+            admin: *admin,
+            program_config: program_config_pda(),
+            market: market_pda(asset_name),
+            system_program: system_program::ID,
+        },
+        ix::InitializeMarket {
+            asset_name: asset_name.to_string(),
+        },
+    )
+}
+
+pub fn advance_market_round(keeper: &Pubkey, asset_name: &str) -> Instruction {
+    instruction(
+        ctx::AdvanceMarketRound {
+            keeper: *keeper,
+            market: market_pda(asset_name),
+        },
+        ix::AdvanceMarketRound {},
+    )
+}
+
+pub fn delegate_market(admin: &Pubkey, asset_name: &str) -> Instruction {
+    let market = market_pda(asset_name);
+    let delegation = delegation_accounts(&market);
+    instruction(
+        ctx::DelegateMarket {
+            admin: *admin,
+            program_config: program_config_pda(),
+            market,
+            buffer_market: delegation.buffer,
+            delegation_record_market: delegation.delegation_record,
+            delegation_metadata_market: delegation.delegation_metadata,
+            owner_program: bs_bet::ID,
+            delegation_program: DELEGATION_PROGRAM_ID,
+            system_program: system_program::ID,
+        },
+        ix::DelegateMarket {
+            asset_name: asset_name.to_string(),
+        },
+    )
+}
+
+pub fn commit_market(payer: &Pubkey, asset_name: &str) -> Instruction {
+    instruction(
+        ctx::CommitMarket {
+            payer: *payer,
+            market: market_pda(asset_name),
+            magic_program: MAGIC_PROGRAM_ID,
+            magic_context: MAGIC_CONTEXT_ID,
+        },
+        ix::CommitMarket {},
+    )
+}
+
+pub fn undelegate_market(admin: &Pubkey, asset_name: &str) -> Instruction {
+    instruction(
+        ctx::UndelegateMarket {
+            admin: *admin,
+            program_config: program_config_pda(),
+            market: market_pda(asset_name),
+            magic_program: MAGIC_PROGRAM_ID,
+            magic_context: MAGIC_CONTEXT_ID,
+        },
+        ix::UndelegateMarket {},
+    )
+}
+
+// --- Commit & undelegation ---
+
+pub fn commit_user_state(user: &Pubkey) -> Instruction {
+    instruction(
+        ctx::CommitUserState {
+            user_authority: *user,
+            user_profile: user_profile_pda(user),
+            active_bet: active_bet_pda(user),
+            magic_program: MAGIC_PROGRAM_ID,
+            magic_context: MAGIC_CONTEXT_ID,
+        },
+        ix::CommitUserState {},
+    )
+}
+
+pub fn commit_user_state_for(keeper: &Pubkey, user: &Pubkey) -> Instruction {
+    instruction(
+        ctx::CommitUserStateFor {
+            keeper: *keeper,
+            user_profile: user_profile_pda(user),
+            active_bet: active_bet_pda(user),
+            magic_program: MAGIC_PROGRAM_ID,
+            magic_context: MAGIC_CONTEXT_ID,
+        },
+        ix::CommitUserStateFor {
+            user_authority_for_pdas: *user,
+        },
+    )
+}
+
+/// `price_feed` settles an expired bet before undelegating, as in [`resolve_bet_normal`];
+/// it may be omitted while the bet is live or once it can be voided.
+pub fn undelegate_from_magicblock(
+    payer: &Pubkey,
+    user: &Pubkey,
+    price_feed: Option<Pubkey>,
+) -> Instruction {
+    instruction(
+        ctx::UndelegateFromMagicBlock {
+            payer: *payer,
+            user_authority: *user,
+            user_auth_state_to_undelegate: user_auth_state_pda(user),
+            user_profile_to_undelegate: user_profile_pda(user),
+            active_bet_to_undelegate: active_bet_pda(user),
+            pyth_price_feed: price_feed,
+            magic_program: MAGIC_PROGRAM_ID,
+            magic_context: MAGIC_CONTEXT_ID,
+        },
+        ix::UndelegateFromMagicblock {},
+    )
+}
+
+pub fn undelegate_inactive_user(
+    keeper: &Pubkey,
+    user: &Pubkey,
+    price_feed: Option<Pubkey>,
+) -> Instruction {
+    instruction(
+        ctx::UndelegateInactiveUser {
+            keeper: *keeper,
+            user_auth_state: user_auth_state_pda(user),
+            user_profile: user_profile_pda(user),
+            active_bet: active_bet_pda(user),
+            pyth_price_feed: price_feed,
+            program_config: program_config_pda(),
+            magic_program: MAGIC_PROGRAM_ID,
+            magic_context: MAGIC_CONTEXT_ID,
+        },
+        ix::UndelegateInactiveUser {
+            user_authority_for_pdas: *user,
+        },
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // `#[commit]` appends `magic_program` then the writable `magic_context` to the context.
+    fn assert_ends_with_magic_accounts(instruction: &Instruction) {
+        let [.., magic_program, magic_context] = &instruction.accounts[..] else {
+            panic!("too few accounts");
+        };
+        assert_eq!(magic_program, &AccountMeta::new_readonly(MAGIC_PROGRAM_ID, false));
+        assert_eq!(magic_context, &AccountMeta::new(MAGIC_CONTEXT_ID, false));
+    }
+
+    #[test]
+    fn ephemeral_bet_builders_end_with_the_magic_accounts() {
+        let user = Pubkey::new_unique();
+        let price_feed = Pubkey::new_unique();
+        let open = open_bet_ephemeral(
+            &user,
+            "SOL/USD",
+            1,
+            100,
+            60,
+            BetTerms::default(),
+            &price_feed,
+            false,
+        );
+        assert_ends_with_magic_accounts(&open);
+        assert_ends_with_magic_accounts(&cancel_bet_ephemeral(&user, &price_feed));
+        for price_feed in [Some(price_feed), None] {
+            let resolve = resolve_bet_ephemeral(&Pubkey::new_unique(), &user, price_feed);
+            assert_ends_with_magic_accounts(&resolve);
+            // An omitted optional account keeps its slot, filled with the program id.
+            assert_eq!(resolve.accounts[4].pubkey, price_feed.unwrap_or(bs_bet::ID));
+        }
+    }
+
+    #[test]
+    fn ephemeral_bet_builders_target_the_users_pdas() {
+        let user = Pubkey::new_unique();
+        let keeper = Pubkey::new_unique();
+        let resolve = resolve_bet_ephemeral(&keeper, &user, None);
+        let keys: Vec<_> = resolve.accounts[..4].iter().map(|meta| meta.pubkey).collect();
+        assert_eq!(
+            keys,
+            [keeper, user_auth_state_pda(&user), user_profile_pda(&user), active_bet_pda(&user)]
+        );
+        assert!(resolve.accounts[0].is_signer);
+    }
+}
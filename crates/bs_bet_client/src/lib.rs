@@ -0,0 +1,14 @@
+//! Rust client for the `bs_bet` program.
+//!
+//! Built on the program crate's `cpi` feature, so account and instruction layouts always
+//! match the deployed program. Builders return plain `Instruction`s; signing and sending is
+//! left to the caller (RPC client, bot framework, ...).
+
+pub mod accounts;
+pub mod instructions;
+pub mod pda;
+
+pub use bs_bet::constants;
+pub use bs_bet::create_delegation_message;
+pub use bs_bet::state::{BetTerms, DelegationArgs, ParlayLegArgs, ProgramConfigArgs, TournamentArgs};
+pub use bs_bet::ID as PROGRAM_ID;
@@ -0,0 +1,140 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+use ephemeral_rollups_sdk::pda::{
+    delegate_buffer_pda_from_delegated_account_and_owner_program,
+    delegation_metadata_pda_from_delegated_account, delegation_record_pda_from_delegated_account,
+};
+
+use bs_bet::constants::*;
+
+fn find(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &bs_bet::ID).0
+}
+
+pub fn user_profile_pda(user: &Pubkey) -> Pubkey {
+    find(&[PROFILE, user.as_ref()])
+}
+
+pub fn user_auth_state_pda(user: &Pubkey) -> Pubkey {
+    find(&[AUTH_STATE, user.as_ref()])
+}
+
+pub fn active_bet_pda(user: &Pubkey) -> Pubkey {
+    find(&[ACTIVE_BET, user.as_ref()])
+}
+
+pub fn program_config_pda() -> Pubkey {
+    find(&[CONFIG])
+}
+
+pub fn recurring_plan_pda(user: &Pubkey) -> Pubkey {
+    find(&[RECURRING_PLAN, user.as_ref()])
+}
+
+pub fn parlay_bet_pda(user: &Pubkey) -> Pubkey {
+    find(&[PARLAY_BET, user.as_ref()])
+}
+
+pub fn challenge_pda(creator: &Pubkey, challenge_id: u64) -> Pubkey {
+    find(&[CHALLENGE, creator.as_ref(), &challenge_id.to_le_bytes()])
+}
+
+pub fn tournament_pda(tournament_id: u64) -> Pubkey {
+    find(&[TOURNAMENT, &tournament_id.to_le_bytes()])
+}
+
+pub fn tournament_entry_pda(tournament: &Pubkey, user: &Pubkey) -> Pubkey {
+    find(&[TOURNAMENT_ENTRY, tournament.as_ref(), user.as_ref()])
+}
+
+pub fn referral_stats_pda(referrer: &Pubkey) -> Pubkey {
+    find(&[REFERRAL_STATS, referrer.as_ref()])
+}
+
+pub fn achievement_pda(id: u16) -> Pubkey {
+    find(&[ACHIEVEMENT, &id.to_le_bytes()])
+}
+
+pub fn achievement_claim_pda(achievement: &Pubkey, user: &Pubkey) -> Pubkey {
+    find(&[ACHIEVEMENT_CLAIM, achievement.as_ref(), user.as_ref()])
+}
+
+pub fn badge_mint_pda(achievement: &Pubkey) -> Pubkey {
+    find(&[BADGE_MINT, achievement.as_ref()])
+}
+
+pub fn market_pda(asset_name: &str) -> Pubkey {
+    find(&[MARKET, asset_name.as_bytes()])
+}
+
+/// Pyth's sponsored push feed for a supported asset; cancels and cash-outs read only this.
+pub fn push_feed_pda(asset_name: &str) -> Option<Pubkey> {
+    bs_bet::push_feed_address(asset_name).ok()
+}
+
+/// The upgradeable-loader program data account, checked by `initialize_program_config`.
+pub fn program_data_address() -> Pubkey {
+    Pubkey::find_program_address(&[bs_bet::ID.as_ref()], &bpf_loader_upgradeable::ID).0
+}
+
+/// Delegation program accounts that accompany a PDA in the `delegate_*` instructions.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DelegationAccounts {
+    pub buffer: Pubkey,
+    pub delegation_record: Pubkey,
+    pub delegation_metadata: Pubkey,
+}
+
+pub fn delegation_accounts(delegated_account: &Pubkey) -> DelegationAccounts {
+    DelegationAccounts {
+        buffer: delegate_buffer_pda_from_delegated_account_and_owner_program(
+            delegated_account,
+            &bs_bet::ID,
+        ),
+        delegation_record: delegation_record_pda_from_delegated_account(delegated_account),
+        delegation_metadata: delegation_metadata_pda_from_delegated_account(delegated_account),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    fn user() -> Pubkey {
+        Pubkey::new_from_array([7; 32])
+    }
+
+    fn address(base58: &str) -> Pubkey {
+        Pubkey::from_str(base58).unwrap()
+    }
+
+    #[test]
+    fn user_pdas_use_the_web_client_seeds() {
+        // Same seeds as `BetForm.tsx` and `tests/bs_bet.ts`.
+        let user = user();
+        let with_seed = |seed: &str| {
+            Pubkey::find_program_address(&[seed.as_bytes(), user.as_ref()], &bs_bet::ID).0
+        };
+        assert_eq!(user_profile_pda(&user), with_seed("profile"));
+        assert_eq!(user_auth_state_pda(&user), with_seed("auth_state"));
+        assert_eq!(active_bet_pda(&user), with_seed("active_bet"));
+    }
+
+    #[test]
+    fn user_pdas_match_known_addresses() {
+        let user = user();
+        assert_eq!(
+            user_profile_pda(&user),
+            address("F1cNzBcRBN5khQhLjVrhuFKnyUwRGCLMd4F5v1cV13Z3")
+        );
+        assert_eq!(
+            user_auth_state_pda(&user),
+            address("48EmB6nQ4smJjEiGNP1i2MmcczPhHq4H1wtVcyAaFqTb")
+        );
+        assert_eq!(
+            active_bet_pda(&user),
+            address("4T9wZKTU63LWWxYHYm1iyy8B95Jfw7hADGPP5kCh7mry")
+        );
+    }
+}
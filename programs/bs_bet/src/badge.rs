@@ -0,0 +1,148 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+use anchor_lang::solana_program::pubkey;
+use anchor_lang::system_program;
+
+// Minimal Token-2022 / associated-token CPI helpers for soulbound achievement badges.
+
+pub const TOKEN_2022_PROGRAM_ID: Pubkey = pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey = pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
+
+// Base mint padded to the token-account length, one account-type byte, and an empty
+// NonTransferable TLV entry (2-byte type + 2-byte length).
+const NON_TRANSFERABLE_MINT_SPACE: usize = 165 + 1 + 2 + 2;
+
+const INITIALIZE_MINT2_TAG: u8 = 20;
+const MINT_TO_TAG: u8 = 7;
+const INITIALIZE_NON_TRANSFERABLE_MINT_TAG: u8 = 32;
+const CREATE_IDEMPOTENT_TAG: u8 = 1;
+
+pub fn associated_token_address(wallet: &Pubkey, mint: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(
+        &[wallet.as_ref(), TOKEN_2022_PROGRAM_ID.as_ref(), mint.as_ref()],
+        &ASSOCIATED_TOKEN_PROGRAM_ID,
+    )
+    .0
+}
+
+/// Creates a 0-decimal, non-transferable mint at the PDA `mint` with `authority` as mint
+/// authority and no freeze authority.
+pub fn create_non_transferable_mint<'info>(
+    payer: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    authority: &Pubkey,
+    token_program: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    mint_signer_seeds: &[&[u8]],
+) -> Result<()> {
+    system_program::create_account(
+        CpiContext::new_with_signer(
+            system_program.clone(),
+            system_program::CreateAccount {
+                from: payer.clone(),
+                to: mint.clone(),
+            },
+            &[mint_signer_seeds],
+        ),
+        Rent::get()?.minimum_balance(NON_TRANSFERABLE_MINT_SPACE),
+        NON_TRANSFERABLE_MINT_SPACE as u64,
+        &TOKEN_2022_PROGRAM_ID,
+    )?;
+
+    // Extensions must be initialised before the mint itself.
+    invoke_signed(
+        &Instruction {
+            program_id: TOKEN_2022_PROGRAM_ID,
+            accounts: vec![AccountMeta::new(mint.key(), false)],
+            data: vec![INITIALIZE_NON_TRANSFERABLE_MINT_TAG],
+        },
+        &[mint.clone(), token_program.clone()],
+        &[],
+    )?;
+
+    let mut data = Vec::with_capacity(1 + 1 + 32 + 1);
+    data.push(INITIALIZE_MINT2_TAG);
+    data.push(0); // decimals
+    data.extend_from_slice(authority.as_ref());
+    data.push(0); // no freeze authority
+    invoke_signed(
+        &Instruction {
+            program_id: TOKEN_2022_PROGRAM_ID,
+            accounts: vec![AccountMeta::new(mint.key(), false)],
+            data,
+        },
+        &[mint.clone(), token_program.clone()],
+        &[],
+    )?;
+    Ok(())
+}
+
+/// Creates `owner`'s associated Token-2022 account for `mint` if it does not exist yet.
+pub fn create_associated_token_account_idempotent<'info>(
+    payer: &AccountInfo<'info>,
+    token_account: &AccountInfo<'info>,
+    owner: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    associated_token_program: &AccountInfo<'info>,
+) -> Result<()> {
+    invoke_signed(
+        &Instruction {
+            program_id: ASSOCIATED_TOKEN_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(payer.key(), true),
+                AccountMeta::new(token_account.key(), false),
+                AccountMeta::new_readonly(owner.key(), false),
+                AccountMeta::new_readonly(mint.key(), false),
+                AccountMeta::new_readonly(system_program.key(), false),
+                AccountMeta::new_readonly(token_program.key(), false),
+            ],
+            data: vec![CREATE_IDEMPOTENT_TAG],
+        },
+        &[
+            payer.clone(),
+            token_account.clone(),
+            owner.clone(),
+            mint.clone(),
+            system_program.clone(),
+            token_program.clone(),
+            associated_token_program.clone(),
+        ],
+        &[],
+    )?;
+    Ok(())
+}
+
+/// Mints one badge token to `token_account`, signed by the PDA mint authority.
+pub fn mint_badge<'info>(
+    mint: &AccountInfo<'info>,
+    token_account: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    authority_signer_seeds: &[&[u8]],
+) -> Result<()> {
+    let mut data = Vec::with_capacity(1 + 8);
+    data.push(MINT_TO_TAG);
+    data.extend_from_slice(&1u64.to_le_bytes());
+    invoke_signed(
+        &Instruction {
+            program_id: TOKEN_2022_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(mint.key(), false),
+                AccountMeta::new(token_account.key(), false),
+                AccountMeta::new_readonly(authority.key(), true),
+            ],
+            data,
+        },
+        &[
+            mint.clone(),
+            token_account.clone(),
+            authority.clone(),
+            token_program.clone(),
+        ],
+        &[authority_signer_seeds],
+    )?;
+    Ok(())
+}
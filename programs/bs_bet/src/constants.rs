@@ -1,7 +1,19 @@
 // --- Pyth ---
 pub const SOL_USD_FEED_ID_HEX: &str =
 	"0xef0d8b6fda2ceba41da15d4095d1da392a0d2f8ed0c6c7bc0f4cfac8c280b56d";
-pub const MAXIMUM_PRICE_AGE_SECONDS: u64 = 3600 * 2; // 2 hours
+pub const BTC_USD_FEED_ID_HEX: &str =
+	"0xe62df6c8b4a85fe1a67db44dc12de5db330f7ac66b72dc658afedf0f4a415b43";
+pub const ETH_USD_FEED_ID_HEX: &str =
+	"0xff61491a931112ddf1bd8147cd1b641375f79f5825126d665480874634fd0ace";
+// Assets available to multi-market bets, with their Pyth feed ids.
+pub const SUPPORTED_ASSETS: [(&str, &str); 3] = [
+	("SOL/USD", SOL_USD_FEED_ID_HEX),
+	("BTC/USD", BTC_USD_FEED_ID_HEX),
+	("ETH/USD", ETH_USD_FEED_ID_HEX),
+];
+pub const MAXIMUM_LIVE_PRICE_AGE_SECONDS: u64 = 30; // samples, barrier checks, cash-outs
+pub const SETTLEMENT_WINDOW_SECONDS: i64 = 60; // settlement prints must be published this soon after expiry
+pub const PRICE_OUTAGE_VOID_SECONDS: i64 = 600; // still unsettled this long past expiry: refund the stake
 
 // --- Sizing helpers ---
 pub const STRING_LENGTH_PREFIX: usize = 4;
@@ -9,8 +21,62 @@ pub const MAX_ASSET_NAME_LENGTH: usize = 20;
 
 // --- App constants ---
 pub const INITIAL_USER_POINTS: u64 = 1000;
+pub const PRICE_SAMPLE_INTERVAL_SECONDS: i64 = 5; // Asian sampling slot length, counted from `opened_at`
+pub const MIN_ASIAN_SAMPLE_COUNT: u32 = 3; // shortest Asian bet, in sampling slots (entry slot included)
+pub const MAX_CANCEL_WINDOW_SECONDS: i64 = 60;
+pub const MAX_DAILY_STREAK_DAYS: u16 = 30;
+pub const REFERRAL_BONUS_BET_COUNT: u32 = 10;
+pub const TRANSFER_WINDOW_SECONDS: i64 = 86_400;
+pub const MIN_COMMIT_FREQUENCY_MS: u32 = 1_000;
+pub const MAX_COMMIT_FREQUENCY_MS: u32 = 3_600_000;
+pub const MARKET_ROUND_SECONDS: i64 = 3_600;
+pub const MAX_REFERRAL_BONUS_BPS: u16 = 1_000;
+pub const MAX_RECURRING_ITERATIONS: u32 = 1_000;
+pub const MIN_PARLAY_LEGS: usize = 2;
+pub const MAX_PARLAY_LEGS: usize = 4;
+pub const MAX_TOURNAMENT_PAYOUT_PLACES: usize = 10;
+pub const MAX_TOURNAMENT_ENTRANTS: u32 = 25; // all entries must fit in one settle_tournament transaction
+pub const TOURNAMENT_ASSET: &str = "SOL/USD";
+
+// --- Achievement kinds ---
+pub const ACHIEVEMENT_KIND_TOTAL_WINS: u8 = 0;
+pub const ACHIEVEMENT_KIND_WIN_STREAK: u8 = 1;
+pub const ACHIEVEMENT_KIND_POINTS: u8 = 2;
+
+// --- Delegation status ---
+pub const DELEGATION_STATUS_UNDELEGATED: u8 = 0;
+pub const DELEGATION_STATUS_PENDING: u8 = 1; // flagged by `manage_delegation`, SDK delegation not landed yet
+pub const DELEGATION_STATUS_DELEGATED: u8 = 2;
+
+// --- Bet types ---
+pub const BET_TYPE_STANDARD: u8 = 0; // single resolution print vs strike
+pub const BET_TYPE_ASIAN: u8 = 1; // average of crank-recorded samples vs strike
+pub const BET_TYPE_TOUCH: u8 = 2; // wins as soon as the barrier is touched before expiry
+pub const BET_TYPE_NO_TOUCH: u8 = 3; // wins if the barrier is never touched before expiry
+pub const BET_TYPE_RANGE: u8 = 4; // expiry price inside (UP=1) or outside (DOWN=0) [lower, upper]
+
+// --- Payouts ---
+pub const BPS_DENOMINATOR: u64 = 10_000;
+pub const EVEN_MONEY_MULTIPLIER_BPS: u64 = 20_000; // 2x, stake included
+pub const MAX_PAYOUT_MULTIPLIER_BPS: u64 = 1_000_000; // 100x
+pub const ANNUALIZED_VOLATILITY_BPS: u64 = 8_000; // 80%, used to price non-even-money bets
+pub const SECONDS_PER_YEAR: i64 = 365 * 24 * 3600;
+pub const MAX_STRIKE_OFFSET_BPS: i32 = 1_000; // strikes within +/-10% of spot
+pub const CASH_OUT_SPREAD_BPS: u64 = 500; // 5% haircut on the fair value of an early exit
+pub const MIN_CASH_OUT_HOLD_SECONDS: i64 = 30; // no exit until the entry print has aged out
 
 // --- PDA seeds ---
 pub const PROFILE: &[u8] = b"profile";
 pub const AUTH_STATE: &[u8] = b"auth_state";
 pub const ACTIVE_BET: &[u8] = b"active_bet";
+pub const CONFIG: &[u8] = b"config";
+pub const RECURRING_PLAN: &[u8] = b"recurring_plan";
+pub const PARLAY_BET: &[u8] = b"parlay_bet";
+pub const CHALLENGE: &[u8] = b"challenge";
+pub const TOURNAMENT: &[u8] = b"tournament";
+pub const TOURNAMENT_ENTRY: &[u8] = b"tournament_entry";
+pub const REFERRAL_STATS: &[u8] = b"referral_stats";
+pub const ACHIEVEMENT: &[u8] = b"achievement";
+pub const ACHIEVEMENT_CLAIM: &[u8] = b"achievement_claim";
+pub const BADGE_MINT: &[u8] = b"badge_mint";
+pub const MARKET: &[u8] = b"market";
@@ -0,0 +1,201 @@
+use anchor_lang::prelude::*;
+
+use crate::badge::{self, ASSOCIATED_TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID};
+use crate::constants::{ACHIEVEMENT, ACHIEVEMENT_CLAIM, BADGE_MINT, CONFIG, PROFILE};
+use crate::constants::{ACHIEVEMENT_KIND_POINTS, ACHIEVEMENT_KIND_TOTAL_WINS, ACHIEVEMENT_KIND_WIN_STREAK};
+use crate::errors::BetError;
+use crate::state::{Achievement, AchievementClaim, ProgramConfig, UserProfile};
+use crate::state::{ACHIEVEMENT_CLAIM_SPACE, ACHIEVEMENT_SPACE};
+
+// Admin defines a milestone and its soulbound badge mint.
+#[derive(Accounts)]
+#[instruction(id: u16)]
+pub struct CreateAchievement<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [CONFIG],
+        bump = program_config.bump,
+        has_one = admin @ BetError::UnauthorizedAdmin
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + ACHIEVEMENT_SPACE,
+        seeds = [ACHIEVEMENT, id.to_le_bytes().as_ref()],
+        bump
+    )]
+    pub achievement: Account<'info, Achievement>,
+    /// CHECK: Created and initialised here as a non-transferable Token-2022 mint.
+    #[account(mut, seeds = [BADGE_MINT, achievement.key().as_ref()], bump)]
+    pub badge_mint: UncheckedAccount<'info>,
+    /// CHECK: Token-2022 program.
+    #[account(address = TOKEN_2022_PROGRAM_ID)]
+    pub token_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateAchievement<'info> {
+    pub fn create_achievement(
+        &mut self,
+        id: u16,
+        kind: u8,
+        threshold: u64,
+        achievement_bump: u8,
+        badge_mint_bump: u8,
+    ) -> Result<()> {
+        validate_achievement(kind, threshold)?;
+
+        let achievement_key = self.achievement.key();
+        let mint_seeds: &[&[u8]] = &[BADGE_MINT, achievement_key.as_ref(), &[badge_mint_bump]];
+        badge::create_non_transferable_mint(
+            &self.admin.to_account_info(),
+            &self.badge_mint.to_account_info(),
+            &achievement_key,
+            &self.token_program.to_account_info(),
+            &self.system_program.to_account_info(),
+            mint_seeds,
+        )?;
+
+        let achievement = &mut self.achievement;
+        achievement.id = id;
+        achievement.kind = kind;
+        achievement.threshold = threshold;
+        achievement.mint = self.badge_mint.key();
+        achievement.claimed_count = 0;
+        achievement.bump = achievement_bump;
+        achievement.mint_bump = badge_mint_bump;
+
+        msg!("Achievement {} created: kind {} threshold {}.", id, kind, threshold);
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct UpdateAchievement<'info> {
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [CONFIG],
+        bump = program_config.bump,
+        has_one = admin @ BetError::UnauthorizedAdmin
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+    #[account(
+        mut,
+        seeds = [ACHIEVEMENT, achievement.id.to_le_bytes().as_ref()],
+        bump = achievement.bump
+    )]
+    pub achievement: Account<'info, Achievement>,
+}
+
+impl<'info> UpdateAchievement<'info> {
+    pub fn update_achievement(&mut self, threshold: u64) -> Result<()> {
+        validate_achievement(self.achievement.kind, threshold)?;
+        self.achievement.threshold = threshold;
+        Ok(())
+    }
+}
+
+// Mints the badge once the profile has reached the milestone. Base layer only: the
+// profile must not be delegated.
+#[derive(Accounts)]
+pub struct ClaimAchievementBadge<'info> {
+    #[account(mut)]
+    pub user_signer: Signer<'info>,
+    #[account(
+        seeds = [PROFILE, user_signer.key().as_ref()],
+        bump = user_profile.bump,
+        constraint = user_profile.authority == user_signer.key() @ BetError::UserProfileAuthorityMismatch
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+    #[account(
+        mut,
+        seeds = [ACHIEVEMENT, achievement.id.to_le_bytes().as_ref()],
+        bump = achievement.bump
+    )]
+    pub achievement: Account<'info, Achievement>,
+    #[account(
+        init,
+        payer = user_signer,
+        space = 8 + ACHIEVEMENT_CLAIM_SPACE,
+        seeds = [ACHIEVEMENT_CLAIM, achievement.key().as_ref(), user_signer.key().as_ref()],
+        bump
+    )]
+    pub achievement_claim: Account<'info, AchievementClaim>,
+    /// CHECK: Badge mint recorded on the achievement.
+    #[account(mut, address = achievement.mint)]
+    pub badge_mint: UncheckedAccount<'info>,
+    /// CHECK: The user's associated Token-2022 account for the badge; checked in the handler.
+    #[account(mut)]
+    pub user_badge_account: UncheckedAccount<'info>,
+    /// CHECK: Token-2022 program.
+    #[account(address = TOKEN_2022_PROGRAM_ID)]
+    pub token_program: UncheckedAccount<'info>,
+    /// CHECK: Associated token account program.
+    #[account(address = ASSOCIATED_TOKEN_PROGRAM_ID)]
+    pub associated_token_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ClaimAchievementBadge<'info> {
+    pub fn claim_achievement_badge(&mut self, achievement_claim_bump: u8) -> Result<()> {
+        let user_key = self.user_signer.key();
+        let profile = &self.user_profile;
+        let achievement = &self.achievement;
+
+        let progress = match achievement.kind {
+            ACHIEVEMENT_KIND_TOTAL_WINS => profile.total_wins as u64,
+            ACHIEVEMENT_KIND_WIN_STREAK => profile.best_win_streak as u64,
+            ACHIEVEMENT_KIND_POINTS => profile.points,
+            _ => return Err(error!(BetError::InvalidAchievement)),
+        };
+        require!(progress >= achievement.threshold, BetError::AchievementNotReached);
+        require_keys_eq!(
+            self.user_badge_account.key(),
+            badge::associated_token_address(&user_key, &achievement.mint),
+            BetError::InvalidBadgeTokenAccount
+        );
+
+        badge::create_associated_token_account_idempotent(
+            &self.user_signer.to_account_info(),
+            &self.user_badge_account.to_account_info(),
+            &self.user_signer.to_account_info(),
+            &self.badge_mint.to_account_info(),
+            &self.system_program.to_account_info(),
+            &self.token_program.to_account_info(),
+            &self.associated_token_program.to_account_info(),
+        )?;
+        let id_bytes = achievement.id.to_le_bytes();
+        let authority_seeds: &[&[u8]] = &[ACHIEVEMENT, id_bytes.as_ref(), &[achievement.bump]];
+        badge::mint_badge(
+            &self.badge_mint.to_account_info(),
+            &self.user_badge_account.to_account_info(),
+            &self.achievement.to_account_info(),
+            &self.token_program.to_account_info(),
+            authority_seeds,
+        )?;
+
+        let achievement = &mut self.achievement;
+        achievement.claimed_count = achievement.claimed_count.saturating_add(1);
+        let claim = &mut self.achievement_claim;
+        claim.achievement = achievement.key();
+        claim.user = user_key;
+        claim.claimed_at = Clock::get()?.unix_timestamp;
+        claim.bump = achievement_claim_bump;
+
+        msg!("Achievement {} badge minted to {}.", achievement.id, user_key);
+        Ok(())
+    }
+}
+
+fn validate_achievement(kind: u8, threshold: u64) -> Result<()> {
+    require!(
+        kind == ACHIEVEMENT_KIND_TOTAL_WINS
+            || kind == ACHIEVEMENT_KIND_WIN_STREAK
+            || kind == ACHIEVEMENT_KIND_POINTS,
+        BetError::InvalidAchievement
+    );
+    require!(threshold > 0, BetError::InvalidAchievement);
+    Ok(())
+}
@@ -1,14 +1,14 @@
 use anchor_lang::prelude::*;
 
 use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
-use pyth_solana_receiver_sdk::price_update::get_feed_id_from_hex;
 
-use crate::constants::{ACTIVE_BET, AUTH_STATE, PROFILE};
-use crate::constants::{MAXIMUM_PRICE_AGE_SECONDS, SOL_USD_FEED_ID_HEX};
+use crate::constants::{ACTIVE_BET, AUTH_STATE, DELEGATION_STATUS_UNDELEGATED, MARKET, PROFILE};
+use crate::constants::{EVEN_MONEY_MULTIPLIER_BPS, MAXIMUM_LIVE_PRICE_AGE_SECONDS};
 use crate::errors::BetError;
-use crate::state::{ActiveBet, UserAuthState, UserProfile, ACTIVE_BET_SPACE};
+use crate::state::{ActiveBet, BetTerms, Market, UserAuthState, UserProfile, ACTIVE_BET_SPACE};
 
 #[derive(Accounts)]
+#[instruction(asset_name_arg: String)]
 pub struct OpenBetNormal<'info> {
     #[account(mut)]
     pub user_signer: Signer<'info>,
@@ -17,7 +17,7 @@ pub struct OpenBetNormal<'info> {
         seeds = [AUTH_STATE, user_signer.key().as_ref()],
         bump = user_auth_state.bump,
         constraint = user_auth_state.user_authority == user_signer.key() @ BetError::UserProfileAuthorityMismatch,
-        constraint = !user_auth_state.is_delegated @ BetError::DelegatedUseQuickBet
+        constraint = user_auth_state.delegation_status == DELEGATION_STATUS_UNDELEGATED @ BetError::DelegatedUseQuickBet
     )]
     pub user_auth_state: Account<'info, UserAuthState>,
     #[account(
@@ -36,6 +36,9 @@ pub struct OpenBetNormal<'info> {
     )]
     pub active_bet: Account<'info, ActiveBet>,
     pub pyth_price_feed: Account<'info, PriceUpdateV2>,
+    // Best-effort market stats (see `Market`); omit while the market is delegated to the rollup.
+    #[account(mut, seeds = [MARKET, asset_name_arg.as_bytes()], bump = market_stats.bump)]
+    pub market_stats: Option<Account<'info, Market>>,
     pub system_program: Program<'info, System>,
 }
 
@@ -46,10 +49,11 @@ impl<'info> OpenBetNormal<'info> {
         direction_arg: u8,
         amount_arg: u64,
         duration_seconds_arg: i64,
+        bet_terms_arg: BetTerms,
         active_bet_bump: u8,
     ) -> Result<()> {
         require!(
-            !self.user_auth_state.is_delegated,
+            self.user_auth_state.delegation_status == DELEGATION_STATUS_UNDELEGATED,
             BetError::DelegatedUseQuickBet
         );
 
@@ -57,80 +61,76 @@ impl<'info> OpenBetNormal<'info> {
         let user_profile = &mut self.user_profile;
         let active_bet = &mut self.active_bet;
 
-        require!(asset_name_arg == "SOL/USD", BetError::UnsupportedAsset);
-        require!(direction_arg == 0 || direction_arg == 1, BetError::InvalidDirection);
-        require!(amount_arg > 0, BetError::ZeroAmount);
-        require!(duration_seconds_arg > 0, BetError::InvalidDuration);
-        require!(user_profile.points >= amount_arg, BetError::InsufficientPoints);
-
-        user_profile.points = user_profile
-            .points
-            .checked_sub(amount_arg)
-            .ok_or_else(|| error!(BetError::InsufficientPoints))?;
-
+        let order = crate::engine::BetOrder {
+            asset_name: asset_name_arg,
+            direction: direction_arg,
+            amount: amount_arg,
+            duration_seconds: duration_seconds_arg,
+            terms: bet_terms_arg,
+        };
         let clock = Clock::get()?;
-        let target_feed_id =
-            get_feed_id_from_hex(SOL_USD_FEED_ID_HEX).map_err(|_| BetError::InvalidPythFeedIdFormat)?;
-        let current_pyth_price_struct = self
-            .pyth_price_feed
-            .get_price_no_older_than(&clock, MAXIMUM_PRICE_AGE_SECONDS, &target_feed_id)
-            .map_err(|_| BetError::PythPriceFeedError)?;
-
-        let initial_price = crate::scale_pyth_price_to_6_decimals(
-            current_pyth_price_struct.price,
-            current_pyth_price_struct.exponent,
+        let initial_price = crate::read_oracle_price(
+            &self.pyth_price_feed,
+            &order.asset_name,
+            &clock,
+            MAXIMUM_LIVE_PRICE_AGE_SECONDS,
         )?;
 
-        active_bet.user = user_key;
-        active_bet.asset_name = asset_name_arg;
-        active_bet.initial_price = initial_price;
-        active_bet.expiry_timestamp = clock
-            .unix_timestamp
-            .checked_add(duration_seconds_arg)
-            .ok_or(BetError::TimestampOverflow)?;
-        active_bet.direction = direction_arg;
-        active_bet.amount_staked = amount_arg;
-        active_bet.resolved_price = 0;
-        active_bet.status = 0;
+        if let Some(market) = self.market_stats.as_mut() {
+            crate::record_market_bet(market, order.direction, order.amount)?;
+        }
+        crate::place_bet(
+            active_bet,
+            user_profile,
+            user_key,
+            order,
+            initial_price,
+            clock.unix_timestamp,
+        )?;
         active_bet.bump = active_bet_bump;
 
         Ok(())
     }
 }
 
+// Permissionless like `resolve_bet_ephemeral`: any `payer` (typically the resolution
+// crank) may settle `user_authority_for_pdas`'s expired bet, so a losing bettor cannot
+// hold settlement back until the bet voids.
 #[derive(Accounts)]
+#[instruction(user_authority_for_pdas: Pubkey)]
 pub struct ResolveBetNormal<'info> {
     #[account(mut)]
-    pub user_signer: Signer<'info>,
+    pub payer: Signer<'info>,
     #[account(
         mut,
-        seeds = [AUTH_STATE, user_signer.key().as_ref()],
+        seeds = [AUTH_STATE, user_authority_for_pdas.as_ref()],
         bump = user_auth_state.bump,
-        constraint = !user_auth_state.is_delegated @ BetError::DelegatedUseQuickBet
+        constraint = user_auth_state.delegation_status == DELEGATION_STATUS_UNDELEGATED @ BetError::DelegatedUseQuickBet
     )]
     pub user_auth_state: Account<'info, UserAuthState>,
     #[account(
         mut,
-        seeds = [PROFILE, user_signer.key().as_ref()],
+        seeds = [PROFILE, user_authority_for_pdas.as_ref()],
         bump = user_profile.bump,
-        constraint = user_profile.authority == user_signer.key() @ BetError::UserProfileAuthorityMismatch
+        constraint = user_profile.authority == user_authority_for_pdas @ BetError::UserProfileAuthorityMismatch
     )]
     pub user_profile: Account<'info, UserProfile>,
     #[account(
         mut,
-        seeds = [ACTIVE_BET, user_signer.key().as_ref()],
+        seeds = [ACTIVE_BET, user_authority_for_pdas.as_ref()],
         bump = active_bet.bump,
-        constraint = active_bet.user == user_signer.key() @ BetError::UserProfileBetUserMismatch
+        constraint = active_bet.user == user_authority_for_pdas @ BetError::UserProfileBetUserMismatch
     )]
     pub active_bet: Account<'info, ActiveBet>,
-    pub pyth_price_feed: Account<'info, PriceUpdateV2>,
+    // Omit only to void a bet nobody settled within `PRICE_OUTAGE_VOID_SECONDS`.
+    pub pyth_price_feed: Option<Account<'info, PriceUpdateV2>>,
     pub clock: Sysvar<'info, Clock>,
 }
 
 impl<'info> ResolveBetNormal<'info> {
     pub fn resolve_bet_normal(&mut self) -> Result<()> {
         require!(
-            !self.user_auth_state.is_delegated,
+            self.user_auth_state.delegation_status == DELEGATION_STATUS_UNDELEGATED,
             BetError::DelegatedUseQuickBet
         );
 
@@ -138,43 +138,47 @@ impl<'info> ResolveBetNormal<'info> {
         let user_profile = &mut self.user_profile;
         let clock = &self.clock;
 
-        require!(bet.status == 0, BetError::BetNotActiveOrAlreadyResolved);
-        require!(
-            clock.unix_timestamp > bet.expiry_timestamp,
-            BetError::BetNotYetExpired
-        );
+        crate::settle_or_void_expired_bet(bet, user_profile, self.pyth_price_feed.as_deref(), clock)?;
 
-        let target_feed_id =
-            get_feed_id_from_hex(SOL_USD_FEED_ID_HEX).map_err(|_| BetError::InvalidPythFeedIdFormat)?;
-        let resolved_price_struct = self
-            .pyth_price_feed
-            .get_price_no_older_than(clock, MAXIMUM_PRICE_AGE_SECONDS, &target_feed_id)
-            .map_err(|_| BetError::PythPriceFeedError)?;
-        bet.resolved_price = crate::scale_pyth_price_to_6_decimals(
-            resolved_price_struct.price,
-            resolved_price_struct.exponent,
-        )?;
+        Ok(())
+    }
+}
 
-        let won = if bet.direction == 1 {
-            bet.resolved_price > bet.initial_price
-        } else {
-            bet.resolved_price < bet.initial_price
-        };
+// Grows an `ActiveBet` created before the bet-type fields were added to the current
+// `ACTIVE_BET_SPACE` (see `grow_legacy_account`). The added fields read as a standard
+// bet; a legacy bet also gets the at-the-money strike and even-money odds it was
+// opened with.
+#[derive(Accounts)]
+pub struct MigrateActiveBet<'info> {
+    #[account(mut)]
+    pub user_signer: Signer<'info>,
+    /// CHECK: legacy-sized `ActiveBet`; owner and discriminator are checked before resizing.
+    #[account(mut, seeds = [ACTIVE_BET, user_signer.key().as_ref()], bump)]
+    pub active_bet: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateActiveBet<'info> {
+    pub fn migrate_active_bet(&mut self) -> Result<()> {
+        let bet_info = self.active_bet.to_account_info();
+        let new_len = 8 + ACTIVE_BET_SPACE;
+        crate::grow_legacy_account(
+            &bet_info,
+            &self.user_signer.to_account_info(),
+            &self.system_program.to_account_info(),
+            ActiveBet::DISCRIMINATOR,
+            new_len,
+        )?;
 
-        if won {
-            bet.status = 1;
-            let payout = bet
-                .amount_staked
-                .checked_mul(2)
-                .ok_or(BetError::PriceCalculationOverflow)?;
-            user_profile.points = user_profile
-                .points
-                .checked_add(payout)
-                .ok_or(BetError::PriceCalculationOverflow)?;
-        } else {
-            bet.status = 2;
+        let mut data = bet_info.try_borrow_mut_data()?;
+        let mut bet = ActiveBet::try_deserialize(&mut &data[..])?;
+        if bet.payout_multiplier_bps == 0 {
+            bet.strike_price = bet.initial_price;
+            bet.payout_multiplier_bps = EVEN_MONEY_MULTIPLIER_BPS;
         }
+        bet.try_serialize(&mut &mut data[..])?;
 
+        msg!("Active bet migrated to {} bytes.", new_len);
         Ok(())
     }
 }
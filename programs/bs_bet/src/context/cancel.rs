@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+
+use crate::constants::{ACTIVE_BET, AUTH_STATE, CONFIG, DELEGATION_STATUS_UNDELEGATED, PROFILE};
+use crate::errors::BetError;
+use crate::state::{ActiveBet, ProgramConfig, UserAuthState, UserProfile};
+
+// Full refund for mis-clicks: only inside the configured window and while the push feed
+// still shows the entry price (see `cancel_live_bet`). Delegated users cancel through
+// `cancel_bet_ephemeral`.
+#[derive(Accounts)]
+pub struct CancelBet<'info> {
+    #[account(mut)]
+    pub user_signer: Signer<'info>,
+    #[account(
+        seeds = [AUTH_STATE, user_signer.key().as_ref()],
+        bump = user_auth_state.bump,
+        constraint = user_auth_state.user_authority == user_signer.key() @ BetError::UserProfileAuthorityMismatch,
+        constraint = user_auth_state.delegation_status == DELEGATION_STATUS_UNDELEGATED @ BetError::DelegatedUseQuickBet
+    )]
+    pub user_auth_state: Account<'info, UserAuthState>,
+    #[account(
+        mut,
+        seeds = [PROFILE, user_signer.key().as_ref()],
+        bump = user_profile.bump,
+        constraint = user_profile.authority == user_signer.key() @ BetError::UserProfileAuthorityMismatch
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+    #[account(
+        mut,
+        seeds = [ACTIVE_BET, user_signer.key().as_ref()],
+        bump = active_bet.bump,
+        constraint = active_bet.user == user_signer.key() @ BetError::UserProfileBetUserMismatch
+    )]
+    pub active_bet: Account<'info, ActiveBet>,
+    #[account(seeds = [CONFIG], bump = program_config.bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+    // Must be the asset's push feed (`push_feed_address`).
+    pub pyth_price_feed: Account<'info, PriceUpdateV2>,
+}
+
+impl<'info> CancelBet<'info> {
+    pub fn cancel_bet(&mut self) -> Result<()> {
+        let clock = Clock::get()?;
+        crate::cancel_live_bet(
+            &mut self.active_bet,
+            &mut self.user_profile,
+            &self.pyth_price_feed,
+            self.program_config.cancel_window_seconds,
+            &clock,
+        )
+    }
+}
@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+
+use crate::constants::{ACTIVE_BET, AUTH_STATE, DELEGATION_STATUS_UNDELEGATED, PROFILE};
+use crate::constants::{BPS_DENOMINATOR, CASH_OUT_SPREAD_BPS, MAXIMUM_LIVE_PRICE_AGE_SECONDS, MIN_CASH_OUT_HOLD_SECONDS};
+use crate::errors::BetError;
+use crate::state::{ActiveBet, UserAuthState, UserProfile};
+
+// Closes a live bet before expiry at its model fair value minus a spread. The exit price
+// comes from the asset's push feed, published after the bet opened, and only once the
+// bet has been held `MIN_CASH_OUT_HOLD_SECONDS`. Base layer only, like `open_bet_normal`: delegated users must disable Quick Bets first.
+#[derive(Accounts)]
+pub struct CashOutBet<'info> {
+    #[account(mut)]
+    pub user_signer: Signer<'info>,
+    #[account(
+        seeds = [AUTH_STATE, user_signer.key().as_ref()],
+        bump = user_auth_state.bump,
+        constraint = user_auth_state.user_authority == user_signer.key() @ BetError::UserProfileAuthorityMismatch,
+        constraint = user_auth_state.delegation_status == DELEGATION_STATUS_UNDELEGATED @ BetError::DelegatedUseQuickBet
+    )]
+    pub user_auth_state: Account<'info, UserAuthState>,
+    #[account(
+        mut,
+        seeds = [PROFILE, user_signer.key().as_ref()],
+        bump = user_profile.bump,
+        constraint = user_profile.authority == user_signer.key() @ BetError::UserProfileAuthorityMismatch
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+    #[account(
+        mut,
+        seeds = [ACTIVE_BET, user_signer.key().as_ref()],
+        bump = active_bet.bump,
+        constraint = active_bet.user == user_signer.key() @ BetError::UserProfileBetUserMismatch
+    )]
+    pub active_bet: Account<'info, ActiveBet>,
+    // The asset's Pyth push feed (`push_feed_address`); any other account is rejected.
+    pub pyth_price_feed: Account<'info, PriceUpdateV2>,
+}
+
+impl<'info> CashOutBet<'info> {
+    pub fn cash_out_bet(&mut self) -> Result<()> {
+        let bet = &mut self.active_bet;
+        let user_profile = &mut self.user_profile;
+        let clock = Clock::get()?;
+
+        require!(bet.status == 0, BetError::BetNotActiveOrAlreadyResolved);
+        require!(
+            clock.unix_timestamp < bet.expiry_timestamp,
+            BetError::BetAlreadyExpired
+        );
+
+        let hold_ends_at = bet
+            .opened_at
+            .checked_add(MIN_CASH_OUT_HOLD_SECONDS)
+            .ok_or(BetError::TimestampOverflow)?;
+        require!(clock.unix_timestamp >= hold_ends_at, BetError::CashOutTooEarly);
+
+        let exit_price = crate::read_push_feed_price(
+            &self.pyth_price_feed,
+            &bet.asset_name,
+            &clock,
+            MAXIMUM_LIVE_PRICE_AGE_SECONDS,
+        )?;
+        require!(
+            exit_price.publish_time > bet.opened_at,
+            BetError::PythPriceTooOldOrUnavailable
+        );
+        let current_price = crate::scale_pyth_price_to_6_decimals(exit_price.price, exit_price.exponent)?;
+
+        let win_probability = crate::engine::live_win_probability(bet, current_price, clock.unix_timestamp)?;
+        let payout = crate::engine::bet_payout(bet)?;
+        let fair_value = (payout as f64 * win_probability).floor() as u64;
+        let cash_out_value = (fair_value as u128)
+            .checked_mul((BPS_DENOMINATOR - CASH_OUT_SPREAD_BPS) as u128)
+            .ok_or(BetError::PriceCalculationOverflow)?
+            / BPS_DENOMINATOR as u128;
+        let cash_out_value =
+            u64::try_from(cash_out_value).map_err(|_| error!(BetError::PriceCalculationOverflow))?;
+        require!(cash_out_value > 0, BetError::NothingToCashOut);
+
+        user_profile.points = user_profile
+            .points
+            .checked_add(cash_out_value)
+            .ok_or(BetError::PriceCalculationOverflow)?;
+        bet.resolved_price = current_price;
+        bet.status = 3; // cancelled
+
+        msg!(
+            "Bet cashed out at price {} for {} points (fair value {}).",
+            current_price,
+            cash_out_value,
+            fair_value
+        );
+        Ok(())
+    }
+}
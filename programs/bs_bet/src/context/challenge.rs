@@ -0,0 +1,265 @@
+use anchor_lang::prelude::*;
+
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+
+use crate::constants::{CHALLENGE, PROFILE};
+use crate::constants::MAXIMUM_LIVE_PRICE_AGE_SECONDS;
+use crate::errors::BetError;
+use crate::state::{Challenge, UserProfile, CHALLENGE_SPACE};
+
+// Peer-to-peer bet: the creator escrows a stake on one direction, an opponent escrows
+// an equal stake on the other, and the winner takes the pot at expiry. No house odds.
+#[derive(Accounts)]
+#[instruction(challenge_id: u64)]
+pub struct CreateChallenge<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [PROFILE, creator.key().as_ref()],
+        bump = creator_profile.bump,
+        constraint = creator_profile.authority == creator.key() @ BetError::UserProfileAuthorityMismatch
+    )]
+    pub creator_profile: Account<'info, UserProfile>,
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + CHALLENGE_SPACE,
+        seeds = [CHALLENGE, creator.key().as_ref(), &challenge_id.to_le_bytes()],
+        bump
+    )]
+    pub challenge: Account<'info, Challenge>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateChallenge<'info> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_challenge(
+        &mut self,
+        challenge_id: u64,
+        asset_name_arg: String,
+        direction_arg: u8,
+        stake_arg: u64,
+        duration_seconds_arg: i64,
+        opponent_arg: Option<Pubkey>,
+        bump: u8,
+    ) -> Result<()> {
+        let creator_key = self.creator.key();
+        let creator_profile = &mut self.creator_profile;
+
+        crate::feed_id_hex_for_asset(&asset_name_arg)?;
+        require!(direction_arg == 0 || direction_arg == 1, BetError::InvalidDirection);
+        require!(stake_arg > 0, BetError::ZeroAmount);
+        require!(duration_seconds_arg > 0, BetError::InvalidDuration);
+        require!(
+            opponent_arg != Some(creator_key),
+            BetError::InvalidChallengeOpponent
+        );
+
+        creator_profile.points = creator_profile
+            .points
+            .checked_sub(stake_arg)
+            .ok_or_else(|| error!(BetError::InsufficientPoints))?;
+
+        let challenge = &mut self.challenge;
+        challenge.challenge_id = challenge_id;
+        challenge.creator = creator_key;
+        challenge.opponent = opponent_arg.unwrap_or_default();
+        challenge.acceptor = Pubkey::default();
+        challenge.asset_name = asset_name_arg;
+        challenge.creator_direction = direction_arg;
+        challenge.stake = stake_arg;
+        challenge.duration_seconds = duration_seconds_arg;
+        challenge.initial_price = 0;
+        challenge.expiry_timestamp = 0;
+        challenge.resolved_price = 0;
+        challenge.status = 0;
+        challenge.winner = Pubkey::default();
+        challenge.bump = bump;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct AcceptChallenge<'info> {
+    #[account(mut)]
+    pub acceptor: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [PROFILE, acceptor.key().as_ref()],
+        bump = acceptor_profile.bump,
+        constraint = acceptor_profile.authority == acceptor.key() @ BetError::UserProfileAuthorityMismatch
+    )]
+    pub acceptor_profile: Account<'info, UserProfile>,
+    #[account(
+        mut,
+        seeds = [CHALLENGE, challenge.creator.as_ref(), &challenge.challenge_id.to_le_bytes()],
+        bump = challenge.bump
+    )]
+    pub challenge: Account<'info, Challenge>,
+    pub pyth_price_feed: Account<'info, PriceUpdateV2>,
+}
+
+impl<'info> AcceptChallenge<'info> {
+    pub fn accept_challenge(&mut self) -> Result<()> {
+        let acceptor_key = self.acceptor.key();
+        let acceptor_profile = &mut self.acceptor_profile;
+        let challenge = &mut self.challenge;
+
+        require!(challenge.status == 0, BetError::ChallengeNotOpen);
+        require!(
+            acceptor_key != challenge.creator
+                && (challenge.opponent == Pubkey::default() || challenge.opponent == acceptor_key),
+            BetError::InvalidChallengeOpponent
+        );
+
+        acceptor_profile.points = acceptor_profile
+            .points
+            .checked_sub(challenge.stake)
+            .ok_or_else(|| error!(BetError::InsufficientPoints))?;
+
+        // The acceptor supplies the entry print, so only a live one is accepted.
+        let clock = Clock::get()?;
+        challenge.acceptor = acceptor_key;
+        challenge.initial_price = crate::read_oracle_price(
+            &self.pyth_price_feed,
+            &challenge.asset_name,
+            &clock,
+            MAXIMUM_LIVE_PRICE_AGE_SECONDS,
+        )?;
+        challenge.expiry_timestamp = clock
+            .unix_timestamp
+            .checked_add(challenge.duration_seconds)
+            .ok_or(BetError::TimestampOverflow)?;
+        challenge.status = 1;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct CancelChallenge<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [PROFILE, creator.key().as_ref()],
+        bump = creator_profile.bump,
+        constraint = creator_profile.authority == creator.key() @ BetError::UserProfileAuthorityMismatch
+    )]
+    pub creator_profile: Account<'info, UserProfile>,
+    #[account(
+        mut,
+        close = creator,
+        seeds = [CHALLENGE, creator.key().as_ref(), &challenge.challenge_id.to_le_bytes()],
+        bump = challenge.bump,
+        has_one = creator @ BetError::UserProfileAuthorityMismatch
+    )]
+    pub challenge: Account<'info, Challenge>,
+}
+
+impl<'info> CancelChallenge<'info> {
+    pub fn cancel_challenge(&mut self) -> Result<()> {
+        require!(self.challenge.status == 0, BetError::ChallengeNotOpen);
+        let creator_profile = &mut self.creator_profile;
+        creator_profile.points = creator_profile
+            .points
+            .checked_add(self.challenge.stake)
+            .ok_or(BetError::PriceCalculationOverflow)?;
+        Ok(())
+    }
+}
+
+// Permissionless once expired: anyone may settle and pay out the pot. The challenge
+// account is closed and its rent returned to the creator who funded it.
+#[derive(Accounts)]
+pub struct SettleChallenge<'info> {
+    #[account(mut)]
+    pub settler: Signer<'info>,
+    /// CHECK: receives the challenge rent; must be the challenge creator
+    #[account(mut, address = challenge.creator @ BetError::UserProfileAuthorityMismatch)]
+    pub creator: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        close = creator,
+        seeds = [CHALLENGE, challenge.creator.as_ref(), &challenge.challenge_id.to_le_bytes()],
+        bump = challenge.bump
+    )]
+    pub challenge: Account<'info, Challenge>,
+    #[account(
+        mut,
+        seeds = [PROFILE, challenge.creator.as_ref()],
+        bump = creator_profile.bump
+    )]
+    pub creator_profile: Account<'info, UserProfile>,
+    #[account(
+        mut,
+        seeds = [PROFILE, challenge.acceptor.as_ref()],
+        bump = acceptor_profile.bump
+    )]
+    pub acceptor_profile: Account<'info, UserProfile>,
+    pub pyth_price_feed: Account<'info, PriceUpdateV2>,
+}
+
+impl<'info> SettleChallenge<'info> {
+    pub fn settle_challenge(&mut self) -> Result<()> {
+        let challenge = &mut self.challenge;
+        let clock = Clock::get()?;
+
+        require!(challenge.status == 1, BetError::ChallengeNotOpen);
+        require!(
+            clock.unix_timestamp > challenge.expiry_timestamp,
+            BetError::BetNotYetExpired
+        );
+
+        // Settlement is permissionless, so the print must come from a short window right
+        // after expiry; neither player can reach back to a favourable earlier price.
+        challenge.resolved_price = crate::read_settlement_price(
+            &self.pyth_price_feed,
+            &challenge.asset_name,
+            challenge.expiry_timestamp,
+        )?;
+
+        let winner_profile = if challenge.resolved_price == challenge.initial_price {
+            None
+        } else {
+            let went_up = challenge.resolved_price > challenge.initial_price;
+            if went_up == (challenge.creator_direction == 1) {
+                challenge.winner = challenge.creator;
+                Some(&mut self.creator_profile)
+            } else {
+                challenge.winner = challenge.acceptor;
+                Some(&mut self.acceptor_profile)
+            }
+        };
+
+        match winner_profile {
+            Some(profile) => {
+                let pot = challenge
+                    .stake
+                    .checked_mul(2)
+                    .ok_or(BetError::PriceCalculationOverflow)?;
+                profile.points = profile
+                    .points
+                    .checked_add(pot)
+                    .ok_or(BetError::PriceCalculationOverflow)?;
+            }
+            None => {
+                for profile in [&mut self.creator_profile, &mut self.acceptor_profile] {
+                    profile.points = profile
+                        .points
+                        .checked_add(challenge.stake)
+                        .ok_or(BetError::PriceCalculationOverflow)?;
+                }
+            }
+        }
+        challenge.status = 2;
+
+        msg!(
+            "Challenge {} settled at {}; winner {}.",
+            challenge.challenge_id,
+            challenge.resolved_price,
+            challenge.winner
+        );
+        Ok(())
+    }
+}
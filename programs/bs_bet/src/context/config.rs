@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{CONFIG, MAX_CANCEL_WINDOW_SECONDS, MAX_DAILY_STREAK_DAYS, MAX_REFERRAL_BONUS_BPS};
+use crate::constants::{MAX_COMMIT_FREQUENCY_MS, MIN_COMMIT_FREQUENCY_MS};
+use crate::errors::BetError;
+use crate::program::BsBet;
+use crate::state::{ProgramConfig, ProgramConfigArgs, PROGRAM_CONFIG_SPACE};
+
+#[derive(Accounts)]
+pub struct InitializeProgramConfig<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + PROGRAM_CONFIG_SPACE,
+        seeds = [CONFIG],
+        bump
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    // Only the upgrade authority may claim the admin role.
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, BsBet>,
+    #[account(
+        constraint = program_data.upgrade_authority_address == Some(admin.key()) @ BetError::UnauthorizedAdmin
+    )]
+    pub program_data: Account<'info, ProgramData>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializeProgramConfig<'info> {
+    pub fn initialize_program_config(&mut self, args: ProgramConfigArgs, bump: u8) -> Result<()> {
+        let config = &mut self.program_config;
+        config.admin = self.admin.key();
+        config.bump = bump;
+        apply_config_args(config, &args)
+    }
+}
+
+#[derive(Accounts)]
+pub struct UpdateProgramConfig<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG],
+        bump = program_config.bump,
+        has_one = admin @ BetError::UnauthorizedAdmin
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+    pub admin: Signer<'info>,
+}
+
+impl<'info> UpdateProgramConfig<'info> {
+    pub fn update_program_config(&mut self, args: ProgramConfigArgs) -> Result<()> {
+        apply_config_args(&mut self.program_config, &args)
+    }
+}
+
+fn apply_config_args(config: &mut ProgramConfig, args: &ProgramConfigArgs) -> Result<()> {
+    require!(
+        (0..=MAX_CANCEL_WINDOW_SECONDS).contains(&args.cancel_window_seconds),
+        BetError::InvalidConfigValue
+    );
+    require!(
+        args.daily_claim_cooldown_seconds > 0,
+        BetError::InvalidConfigValue
+    );
+    require!(
+        args.daily_streak_max <= MAX_DAILY_STREAK_DAYS,
+        BetError::InvalidConfigValue
+    );
+    require!(
+        args.referral_bonus_bps <= MAX_REFERRAL_BONUS_BPS,
+        BetError::InvalidConfigValue
+    );
+    require!(
+        (MIN_COMMIT_FREQUENCY_MS..=MAX_COMMIT_FREQUENCY_MS)
+            .contains(&args.delegation_commit_frequency_ms),
+        BetError::InvalidConfigValue
+    );
+    require!(args.max_delegation_seconds >= 0, BetError::InvalidConfigValue);
+    require!(args.inactivity_timeout_seconds >= 0, BetError::InvalidConfigValue);
+    config.cancel_window_seconds = args.cancel_window_seconds;
+    config.daily_points_amount = args.daily_points_amount;
+    config.daily_claim_cooldown_seconds = args.daily_claim_cooldown_seconds;
+    config.daily_streak_bonus_bps = args.daily_streak_bonus_bps;
+    config.daily_streak_max = args.daily_streak_max;
+    config.referral_bonus_bps = args.referral_bonus_bps;
+    config.points_transfers_enabled = args.points_transfers_enabled;
+    config.daily_transfer_cap = args.daily_transfer_cap;
+    config.delegation_commit_frequency_ms = args.delegation_commit_frequency_ms;
+    config.delegation_validator = args.delegation_validator;
+    config.max_delegation_seconds = args.max_delegation_seconds;
+    config.inactivity_timeout_seconds = args.inactivity_timeout_seconds;
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{CONFIG, MARKET, MARKET_ROUND_SECONDS};
+use crate::errors::BetError;
+use crate::state::{Market, ProgramConfig, MARKET_SPACE};
+
+#[derive(Accounts)]
+#[instruction(asset_name: String)]
+pub struct InitializeMarket<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [CONFIG],
+        bump = program_config.bump,
+        has_one = admin @ BetError::UnauthorizedAdmin
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + MARKET_SPACE,
+        seeds = [MARKET, asset_name.as_bytes()],
+        bump
+    )]
+    pub market: Account<'info, Market>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> InitializeMarket<'info> {
+    pub fn initialize_market(&mut self, asset_name: String, market_bump: u8) -> Result<()> {
+        crate::feed_id_hex_for_asset(&asset_name)?;
+        let market = &mut self.market;
+        market.asset_name = asset_name;
+        market.round_id = 1;
+        market.round_started_at = Clock::get()?.unix_timestamp;
+        market.bump = market_bump;
+        Ok(())
+    }
+}
+
+// Permissionless crank that rolls the market into its next round. Works on the base
+// layer and, while the market is delegated, on the ephemeral rollup.
+#[derive(Accounts)]
+pub struct AdvanceMarketRound<'info> {
+    pub keeper: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [MARKET, market.asset_name.as_bytes()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+}
+
+impl<'info> AdvanceMarketRound<'info> {
+    pub fn advance_market_round(&mut self) -> Result<()> {
+        let market = &mut self.market;
+        let now = Clock::get()?.unix_timestamp;
+        let round_ends_at = market
+            .round_started_at
+            .checked_add(MARKET_ROUND_SECONDS)
+            .ok_or(BetError::TimestampOverflow)?;
+        require!(now >= round_ends_at, BetError::MarketRoundNotFinished);
+
+        msg!(
+            "Market {} round {} closed: up {} / down {} over {} bets.",
+            market.asset_name,
+            market.round_id,
+            market.round_up_stake,
+            market.round_down_stake,
+            market.round_bet_count
+        );
+        market.round_id = market.round_id.saturating_add(1);
+        market.round_started_at = now;
+        market.round_up_stake = 0;
+        market.round_down_stake = 0;
+        market.round_bet_count = 0;
+        Ok(())
+    }
+}
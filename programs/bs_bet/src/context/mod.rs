@@ -1,7 +1,33 @@
 pub mod profile;
+pub mod achievement;
 pub mod bet;
+pub mod cancel;
+pub mod cash_out;
+pub mod challenge;
+pub mod config;
+pub mod market;
+pub mod parlay;
+pub mod recurring;
+pub mod referral;
+pub mod roll;
 pub mod rollup;
+pub mod sample;
+pub mod tournament;
+pub mod transfer;
 
 pub use profile::*;
+pub use achievement::*;
 pub use bet::*;
-pub use rollup::*;
\ No newline at end of file
+pub use cancel::*;
+pub use cash_out::*;
+pub use challenge::*;
+pub use config::*;
+pub use market::*;
+pub use parlay::*;
+pub use recurring::*;
+pub use referral::*;
+pub use roll::*;
+pub use rollup::*;
+pub use sample::*;
+pub use tournament::*;
+pub use transfer::*;
\ No newline at end of file
@@ -0,0 +1,204 @@
+use anchor_lang::prelude::*;
+
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+
+use crate::constants::{PARLAY_BET, PROFILE};
+use crate::constants::{BPS_DENOMINATOR, EVEN_MONEY_MULTIPLIER_BPS, MAXIMUM_LIVE_PRICE_AGE_SECONDS, MAX_PARLAY_LEGS, MAX_PAYOUT_MULTIPLIER_BPS, MIN_PARLAY_LEGS};
+use crate::errors::BetError;
+use crate::state::{ParlayBet, ParlayLeg, ParlayLegArgs, UserProfile, PARLAY_BET_SPACE};
+
+// Multi-market bet: every leg shares one expiry and all legs must win.
+// Price updates are passed as remaining accounts, one per leg in leg order.
+#[derive(Accounts)]
+pub struct OpenParlayBet<'info> {
+    #[account(mut)]
+    pub user_signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [PROFILE, user_signer.key().as_ref()],
+        bump = user_profile.bump,
+        constraint = user_profile.authority == user_signer.key() @ BetError::UserProfileAuthorityMismatch
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+    #[account(
+        init_if_needed,
+        payer = user_signer,
+        space = 8 + PARLAY_BET_SPACE,
+        seeds = [PARLAY_BET, user_signer.key().as_ref()],
+        bump
+    )]
+    pub parlay_bet: Account<'info, ParlayBet>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> OpenParlayBet<'info> {
+    pub fn open_parlay_bet(
+        &mut self,
+        legs_arg: Vec<ParlayLegArgs>,
+        amount_arg: u64,
+        duration_seconds_arg: i64,
+        price_accounts: &[AccountInfo<'info>],
+        parlay_bet_bump: u8,
+    ) -> Result<()> {
+        let user_key = self.user_signer.key();
+        let user_profile = &mut self.user_profile;
+        let parlay = &mut self.parlay_bet;
+
+        require!(
+            (MIN_PARLAY_LEGS..=MAX_PARLAY_LEGS).contains(&legs_arg.len()),
+            BetError::InvalidParlayLegs
+        );
+        for (i, leg) in legs_arg.iter().enumerate() {
+            crate::feed_id_hex_for_asset(&leg.asset_name)?;
+            require!(leg.direction == 0 || leg.direction == 1, BetError::InvalidDirection);
+            require!(
+                legs_arg[..i].iter().all(|other| other.asset_name != leg.asset_name),
+                BetError::InvalidParlayLegs
+            );
+        }
+        require!(
+            price_accounts.len() == legs_arg.len(),
+            BetError::ParlayPriceAccountsMismatch
+        );
+        require!(amount_arg > 0, BetError::ZeroAmount);
+        require!(duration_seconds_arg > 0, BetError::InvalidDuration);
+        require!(
+            parlay.user == Pubkey::default() || parlay.status != 0,
+            BetError::BetStillActive
+        );
+        require!(user_profile.points >= amount_arg, BetError::InsufficientPoints);
+
+        // Each leg is even money, so the combined odds double per leg.
+        let mut payout_multiplier_bps = EVEN_MONEY_MULTIPLIER_BPS;
+        for _ in 1..legs_arg.len() {
+            payout_multiplier_bps = payout_multiplier_bps
+                .checked_mul(EVEN_MONEY_MULTIPLIER_BPS)
+                .ok_or(BetError::PriceCalculationOverflow)?
+                / BPS_DENOMINATOR;
+        }
+        require!(
+            payout_multiplier_bps <= MAX_PAYOUT_MULTIPLIER_BPS,
+            BetError::PayoutOutOfRange
+        );
+
+        let clock = Clock::get()?;
+        let mut legs = Vec::with_capacity(legs_arg.len());
+        for (leg, price_account) in legs_arg.into_iter().zip(price_accounts.iter()) {
+            let initial_price = crate::read_oracle_price(
+                &load_price_update(price_account)?,
+                &leg.asset_name,
+                &clock,
+                MAXIMUM_LIVE_PRICE_AGE_SECONDS,
+            )?;
+            legs.push(ParlayLeg {
+                asset_name: leg.asset_name,
+                direction: leg.direction,
+                initial_price,
+                resolved_price: 0,
+            });
+        }
+
+        user_profile.points = user_profile
+            .points
+            .checked_sub(amount_arg)
+            .ok_or_else(|| error!(BetError::InsufficientPoints))?;
+
+        parlay.user = user_key;
+        parlay.legs = legs;
+        parlay.amount_staked = amount_arg;
+        parlay.expiry_timestamp = clock
+            .unix_timestamp
+            .checked_add(duration_seconds_arg)
+            .ok_or(BetError::TimestampOverflow)?;
+        parlay.payout_multiplier_bps = payout_multiplier_bps;
+        parlay.status = 0;
+        parlay.opened_at = clock.unix_timestamp;
+        parlay.bump = parlay_bet_bump;
+
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct ResolveParlayBet<'info> {
+    #[account(mut)]
+    pub user_signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [PROFILE, user_signer.key().as_ref()],
+        bump = user_profile.bump,
+        constraint = user_profile.authority == user_signer.key() @ BetError::UserProfileAuthorityMismatch
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+    #[account(
+        mut,
+        seeds = [PARLAY_BET, user_signer.key().as_ref()],
+        bump = parlay_bet.bump,
+        constraint = parlay_bet.user == user_signer.key() @ BetError::UserProfileBetUserMismatch
+    )]
+    pub parlay_bet: Account<'info, ParlayBet>,
+}
+
+impl<'info> ResolveParlayBet<'info> {
+    pub fn resolve_parlay_bet(&mut self, price_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        let user_profile = &mut self.user_profile;
+        let parlay = &mut self.parlay_bet;
+        let clock = Clock::get()?;
+
+        require!(parlay.status == 0, BetError::BetNotActiveOrAlreadyResolved);
+        require!(
+            clock.unix_timestamp > parlay.expiry_timestamp,
+            BetError::BetNotYetExpired
+        );
+        require!(
+            price_accounts.len() == parlay.legs.len(),
+            BetError::ParlayPriceAccountsMismatch
+        );
+
+        let expiry_timestamp = parlay.expiry_timestamp;
+        let mut all_legs_won = true;
+        for (leg, price_account) in parlay.legs.iter_mut().zip(price_accounts.iter()) {
+            // Each leg settles on a print published after the shared expiry.
+            leg.resolved_price = crate::read_settlement_price(
+                &load_price_update(price_account)?,
+                &leg.asset_name,
+                expiry_timestamp,
+            )?;
+            let leg_won = if leg.direction == 1 {
+                leg.resolved_price > leg.initial_price
+            } else {
+                leg.resolved_price < leg.initial_price
+            };
+            all_legs_won &= leg_won;
+        }
+
+        if all_legs_won {
+            parlay.status = 1;
+            let payout = (parlay.amount_staked as u128)
+                .checked_mul(parlay.payout_multiplier_bps as u128)
+                .ok_or(BetError::PriceCalculationOverflow)?
+                / BPS_DENOMINATOR as u128;
+            let payout =
+                u64::try_from(payout).map_err(|_| error!(BetError::PriceCalculationOverflow))?;
+            user_profile.points = user_profile
+                .points
+                .checked_add(payout)
+                .ok_or(BetError::PriceCalculationOverflow)?;
+        } else {
+            parlay.status = 2;
+        }
+        crate::record_bet_outcome(user_profile, all_legs_won);
+
+        Ok(())
+    }
+}
+
+fn load_price_update(price_account: &AccountInfo) -> Result<PriceUpdateV2> {
+    require_keys_eq!(
+        *price_account.owner,
+        PriceUpdateV2::owner(),
+        BetError::PythPriceFeedError
+    );
+    let data = price_account.try_borrow_data()?;
+    PriceUpdateV2::try_deserialize(&mut &data[..]).map_err(|_| error!(BetError::PythPriceFeedError))
+}
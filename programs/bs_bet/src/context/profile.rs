@@ -1,9 +1,13 @@
 use anchor_lang::prelude::*;
 
-use crate::constants::{ACTIVE_BET, AUTH_STATE, PROFILE};
-use crate::state::{ActiveBet, UserAuthState, UserProfile, ACTIVE_BET_SPACE, USER_AUTH_STATE_SPACE, USER_PROFILE_SPACE};
+use crate::constants::{ACTIVE_BET, AUTH_STATE, CONFIG, PROFILE, REFERRAL_STATS};
+use crate::constants::BPS_DENOMINATOR;
+use crate::errors::BetError;
+use crate::state::{ActiveBet, ProgramConfig, ReferralStats, UserAuthState, UserProfile};
+use crate::state::{ACTIVE_BET_SPACE, REFERRAL_STATS_SPACE, USER_AUTH_STATE_SPACE, USER_PROFILE_SPACE};
 
 #[derive(Accounts)]
+#[instruction(referrer: Option<Pubkey>)]
 pub struct CreateUserProfile<'info> {
     #[account(
         init_if_needed,
@@ -29,7 +33,167 @@ pub struct CreateUserProfile<'info> {
         bump
     )]
     pub active_bet: Account<'info, ActiveBet>,
+    // Pass exactly when `referrer` is set; a referrer is only accepted for a new profile.
+    #[account(
+        init_if_needed,
+        payer = user_authority,
+        space = 8 + REFERRAL_STATS_SPACE,
+        seeds = [REFERRAL_STATS, referrer.unwrap_or_default().as_ref()],
+        bump
+    )]
+    pub referral_stats: Option<Account<'info, ReferralStats>>,
     #[account(mut)]
     pub user_authority: Signer<'info>,
     pub system_program: Program<'info, System>,
-}
\ No newline at end of file
+}
+
+// Faucet so users at zero points are never locked out. Works on the base layer and,
+// for a delegated profile, on the ephemeral rollup.
+#[derive(Accounts)]
+pub struct ClaimDailyPoints<'info> {
+    #[account(mut)]
+    pub user_signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [PROFILE, user_signer.key().as_ref()],
+        bump = user_profile.bump,
+        constraint = user_profile.authority == user_signer.key() @ BetError::UserProfileAuthorityMismatch
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+    #[account(seeds = [CONFIG], bump = program_config.bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+}
+
+impl<'info> ClaimDailyPoints<'info> {
+    pub fn claim_daily_points(&mut self) -> Result<()> {
+        let config = &self.program_config;
+        let user_profile = &mut self.user_profile;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(config.daily_points_amount > 0, BetError::DailyClaimDisabled);
+        let next_claim_at = user_profile
+            .last_daily_claim_timestamp
+            .checked_add(config.daily_claim_cooldown_seconds)
+            .ok_or(BetError::TimestampOverflow)?;
+        require!(
+            user_profile.last_daily_claim_timestamp == 0 || now >= next_claim_at,
+            BetError::DailyClaimCooldown
+        );
+
+        // Claiming within one cooldown of becoming eligible keeps the streak alive.
+        let streak_deadline = next_claim_at
+            .checked_add(config.daily_claim_cooldown_seconds)
+            .ok_or(BetError::TimestampOverflow)?;
+        user_profile.daily_claim_streak =
+            if user_profile.last_daily_claim_timestamp != 0 && now <= streak_deadline {
+                user_profile
+                    .daily_claim_streak
+                    .saturating_add(1)
+                    .min(config.daily_streak_max.max(1))
+            } else {
+                1
+            };
+
+        let bonus_bps = (config.daily_streak_bonus_bps as u64)
+            .checked_mul(user_profile.daily_claim_streak.saturating_sub(1) as u64)
+            .ok_or(BetError::PriceCalculationOverflow)?;
+        let amount = (config.daily_points_amount as u128)
+            .checked_mul((BPS_DENOMINATOR + bonus_bps) as u128)
+            .ok_or(BetError::PriceCalculationOverflow)?
+            / BPS_DENOMINATOR as u128;
+        let amount = u64::try_from(amount).map_err(|_| error!(BetError::PriceCalculationOverflow))?;
+
+        user_profile.points = user_profile
+            .points
+            .checked_add(amount)
+            .ok_or(BetError::PriceCalculationOverflow)?;
+        user_profile.last_daily_claim_timestamp = now;
+
+        msg!(
+            "Daily points claimed: {} (streak {}).",
+            amount,
+            user_profile.daily_claim_streak
+        );
+        Ok(())
+    }
+}
+
+// Grows a `UserProfile` created before the faucet, referral, streak and transfer fields
+// to the current `USER_PROFILE_SPACE` (see `grow_legacy_account`). Every added field
+// starts at zero / `None`, which is also what a new profile starts with.
+#[derive(Accounts)]
+pub struct MigrateUserProfile<'info> {
+    #[account(mut)]
+    pub user_signer: Signer<'info>,
+    /// CHECK: legacy-sized `UserProfile`; owner and discriminator are checked before resizing.
+    #[account(mut, seeds = [PROFILE, user_signer.key().as_ref()], bump)]
+    pub user_profile: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateUserProfile<'info> {
+    pub fn migrate_user_profile(&mut self) -> Result<()> {
+        let profile_info = self.user_profile.to_account_info();
+        let new_len = 8 + USER_PROFILE_SPACE;
+        crate::grow_legacy_account(
+            &profile_info,
+            &self.user_signer.to_account_info(),
+            &self.system_program.to_account_info(),
+            UserProfile::DISCRIMINATOR,
+            new_len,
+        )?;
+
+        let data = profile_info.try_borrow_data()?;
+        let user_profile = UserProfile::try_deserialize(&mut &data[..])?;
+        require_keys_eq!(
+            user_profile.authority,
+            self.user_signer.key(),
+            BetError::UserProfileAuthorityMismatch
+        );
+
+        msg!("User profile migrated to {} bytes.", new_len);
+        Ok(())
+    }
+}
+
+// Grows a `UserAuthState` created before the delegation preference, lifetime and
+// activity fields to the current `USER_AUTH_STATE_SPACE` (see `grow_legacy_account`).
+// The added fields read as "use the program defaults"; the old `is_delegated` flag is
+// mapped onto `delegation_status`.
+#[derive(Accounts)]
+pub struct MigrateUserAuthState<'info> {
+    #[account(mut)]
+    pub user_signer: Signer<'info>,
+    /// CHECK: legacy-sized `UserAuthState`; owner and discriminator are checked before resizing.
+    #[account(mut, seeds = [AUTH_STATE, user_signer.key().as_ref()], bump)]
+    pub user_auth_state: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> MigrateUserAuthState<'info> {
+    pub fn migrate_user_auth_state(&mut self) -> Result<()> {
+        let auth_info = self.user_auth_state.to_account_info();
+        let new_len = 8 + USER_AUTH_STATE_SPACE;
+        crate::grow_legacy_account(
+            &auth_info,
+            &self.user_signer.to_account_info(),
+            &self.system_program.to_account_info(),
+            UserAuthState::DISCRIMINATOR,
+            new_len,
+        )?;
+
+        let mut data = auth_info.try_borrow_mut_data()?;
+        let mut auth_state = UserAuthState::try_deserialize(&mut &data[..])?;
+        require_keys_eq!(
+            auth_state.user_authority,
+            self.user_signer.key(),
+            BetError::UserProfileAuthorityMismatch
+        );
+        auth_state.delegation_status =
+            crate::delegation_status_from_legacy_flag(auth_state.delegation_status);
+        auth_state.try_serialize(&mut &mut data[..])?;
+
+        msg!("User auth state migrated to {} bytes.", new_len);
+        Ok(())
+    }
+}
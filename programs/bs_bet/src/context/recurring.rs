@@ -0,0 +1,191 @@
+use anchor_lang::prelude::*;
+
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+
+use crate::constants::{ACTIVE_BET, PROFILE, RECURRING_PLAN};
+use crate::constants::{MAXIMUM_LIVE_PRICE_AGE_SECONDS, MAX_RECURRING_ITERATIONS};
+use crate::errors::BetError;
+use crate::state::{ActiveBet, BetTerms, RecurringBetPlan, UserProfile, RECURRING_BET_PLAN_SPACE};
+
+#[derive(Accounts)]
+pub struct CreateRecurringPlan<'info> {
+    #[account(mut)]
+    pub user_signer: Signer<'info>,
+    #[account(
+        init,
+        payer = user_signer,
+        space = 8 + RECURRING_BET_PLAN_SPACE,
+        seeds = [RECURRING_PLAN, user_signer.key().as_ref()],
+        bump
+    )]
+    pub recurring_plan: Account<'info, RecurringBetPlan>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateRecurringPlan<'info> {
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_recurring_plan(
+        &mut self,
+        asset_name_arg: String,
+        direction_arg: u8,
+        amount_arg: u64,
+        duration_seconds_arg: i64,
+        max_iterations_arg: u32,
+        stop_loss_points_arg: u64,
+        bump: u8,
+    ) -> Result<()> {
+        crate::engine::validate_bet_order(&crate::engine::BetOrder {
+            asset_name: asset_name_arg.clone(),
+            direction: direction_arg,
+            amount: amount_arg,
+            duration_seconds: duration_seconds_arg,
+            terms: BetTerms::default(),
+        })?;
+        require!(
+            max_iterations_arg > 0 && max_iterations_arg <= MAX_RECURRING_ITERATIONS,
+            BetError::InvalidPlan
+        );
+
+        let plan = &mut self.recurring_plan;
+        plan.user = self.user_signer.key();
+        plan.asset_name = asset_name_arg;
+        plan.direction = direction_arg;
+        plan.amount_staked = amount_arg;
+        plan.duration_seconds = duration_seconds_arg;
+        plan.max_iterations = max_iterations_arg;
+        plan.iterations_executed = 0;
+        plan.stop_loss_points = stop_loss_points_arg;
+        plan.status = 0;
+        plan.last_executed_at = 0;
+        plan.bump = bump;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct ManageRecurringPlan<'info> {
+    #[account(mut)]
+    pub user_signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [RECURRING_PLAN, user_signer.key().as_ref()],
+        bump = recurring_plan.bump,
+        constraint = recurring_plan.user == user_signer.key() @ BetError::UserProfileAuthorityMismatch
+    )]
+    pub recurring_plan: Account<'info, RecurringBetPlan>,
+}
+
+impl<'info> ManageRecurringPlan<'info> {
+    pub fn set_recurring_plan_paused(&mut self, paused: bool) -> Result<()> {
+        let plan = &mut self.recurring_plan;
+        require!(plan.status != 2, BetError::PlanNotActive);
+        plan.status = if paused { 1 } else { 0 };
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct CancelRecurringPlan<'info> {
+    #[account(mut)]
+    pub user_signer: Signer<'info>,
+    #[account(
+        mut,
+        close = user_signer,
+        seeds = [RECURRING_PLAN, user_signer.key().as_ref()],
+        bump = recurring_plan.bump,
+        constraint = recurring_plan.user == user_signer.key() @ BetError::UserProfileAuthorityMismatch
+    )]
+    pub recurring_plan: Account<'info, RecurringBetPlan>,
+}
+
+// Keeper crank: settles the previous bet (once expired) and opens the next one.
+// Base layer only, since the plan account is never delegated.
+#[derive(Accounts)]
+#[instruction(user_authority_for_pdas: Pubkey)]
+pub struct ExecuteRecurringPlan<'info> {
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [RECURRING_PLAN, user_authority_for_pdas.as_ref()],
+        bump = recurring_plan.bump,
+        constraint = recurring_plan.user == user_authority_for_pdas @ BetError::UserProfileAuthorityMismatch
+    )]
+    pub recurring_plan: Account<'info, RecurringBetPlan>,
+    #[account(
+        mut,
+        seeds = [PROFILE, user_authority_for_pdas.as_ref()],
+        bump = user_profile.bump,
+        constraint = user_profile.authority == user_authority_for_pdas @ BetError::UserProfileAuthorityMismatch
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+    #[account(
+        mut,
+        seeds = [ACTIVE_BET, user_authority_for_pdas.as_ref()],
+        bump = active_bet.bump,
+        constraint = active_bet.user == user_authority_for_pdas @ BetError::UserProfileBetUserMismatch
+    )]
+    pub active_bet: Account<'info, ActiveBet>,
+    pub pyth_price_feed: Account<'info, PriceUpdateV2>,
+}
+
+impl<'info> ExecuteRecurringPlan<'info> {
+    pub fn execute_recurring_plan(&mut self, user_authority_for_pdas: Pubkey) -> Result<()> {
+        let plan = &mut self.recurring_plan;
+        let user_profile = &mut self.user_profile;
+        let active_bet = &mut self.active_bet;
+
+        require!(plan.status == 0, BetError::PlanNotActive);
+
+        // The keeper supplies the price, so the previous bet only settles on a print from
+        // after its expiry and the next one only opens on a live print.
+        let clock = Clock::get()?;
+        if active_bet.status == 0 {
+            let resolution_price = crate::read_settlement_price(
+                &self.pyth_price_feed,
+                &active_bet.asset_name,
+                active_bet.expiry_timestamp,
+            )?;
+            crate::settle_expired_bet(active_bet, user_profile, resolution_price, clock.unix_timestamp)?;
+        }
+        plan.last_executed_at = clock.unix_timestamp;
+
+        let stop_loss_hit = user_profile.points <= plan.stop_loss_points
+            || user_profile.points < plan.amount_staked;
+        if plan.iterations_executed >= plan.max_iterations || stop_loss_hit {
+            plan.status = 2;
+            msg!(
+                "Recurring plan completed after {} iterations (points {}).",
+                plan.iterations_executed,
+                user_profile.points
+            );
+            return Ok(());
+        }
+
+        let initial_price = crate::read_oracle_price(
+            &self.pyth_price_feed,
+            &plan.asset_name,
+            &clock,
+            MAXIMUM_LIVE_PRICE_AGE_SECONDS,
+        )?;
+        crate::place_bet(
+            active_bet,
+            user_profile,
+            user_authority_for_pdas,
+            crate::engine::BetOrder {
+                asset_name: plan.asset_name.clone(),
+                direction: plan.direction,
+                amount: plan.amount_staked,
+                duration_seconds: plan.duration_seconds,
+                terms: BetTerms::default(),
+            },
+            initial_price,
+            clock.unix_timestamp,
+        )?;
+        plan.iterations_executed = plan
+            .iterations_executed
+            .checked_add(1)
+            .ok_or(BetError::PriceCalculationOverflow)?;
+        Ok(())
+    }
+}
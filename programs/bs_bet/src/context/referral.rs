@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{BPS_DENOMINATOR, CONFIG, PROFILE, REFERRAL_STATS};
+use crate::errors::BetError;
+use crate::state::{ProgramConfig, ReferralStats, UserProfile};
+
+// Pays the referrer their share of a referee's qualifying stake. Both profiles must be
+// on the same layer, so run this on the base layer unless both are delegated.
+#[derive(Accounts)]
+#[instruction(referee: Pubkey)]
+pub struct ClaimReferralReward<'info> {
+    #[account(mut)]
+    pub referrer_signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [PROFILE, referrer_signer.key().as_ref()],
+        bump = referrer_profile.bump,
+        constraint = referrer_profile.authority == referrer_signer.key() @ BetError::UserProfileAuthorityMismatch
+    )]
+    pub referrer_profile: Account<'info, UserProfile>,
+    #[account(
+        mut,
+        seeds = [PROFILE, referee.as_ref()],
+        bump = referee_profile.bump,
+        constraint = referee_profile.referrer == Some(referrer_signer.key()) @ BetError::InvalidReferrer
+    )]
+    pub referee_profile: Account<'info, UserProfile>,
+    #[account(
+        mut,
+        seeds = [REFERRAL_STATS, referrer_signer.key().as_ref()],
+        bump = referral_stats.bump
+    )]
+    pub referral_stats: Account<'info, ReferralStats>,
+    #[account(seeds = [CONFIG], bump = program_config.bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+}
+
+impl<'info> ClaimReferralReward<'info> {
+    pub fn claim_referral_reward(&mut self, _referee: Pubkey) -> Result<()> {
+        let referee_profile = &mut self.referee_profile;
+        let qualifying_stake = referee_profile.unclaimed_referral_stake;
+
+        let reward = (qualifying_stake as u128)
+            .checked_mul(self.program_config.referral_bonus_bps as u128)
+            .ok_or(BetError::PriceCalculationOverflow)?
+            / BPS_DENOMINATOR as u128;
+        let reward = u64::try_from(reward).map_err(|_| error!(BetError::PriceCalculationOverflow))?;
+        require!(reward > 0, BetError::NoReferralReward);
+
+        referee_profile.unclaimed_referral_stake = 0;
+        let referrer_profile = &mut self.referrer_profile;
+        referrer_profile.points = referrer_profile
+            .points
+            .checked_add(reward)
+            .ok_or(BetError::PriceCalculationOverflow)?;
+        let stats = &mut self.referral_stats;
+        stats.earned_rewards = stats
+            .earned_rewards
+            .checked_add(reward)
+            .ok_or(BetError::PriceCalculationOverflow)?;
+
+        msg!(
+            "Referral reward of {} points paid for {} qualifying stake.",
+            reward,
+            qualifying_stake
+        );
+        Ok(())
+    }
+}
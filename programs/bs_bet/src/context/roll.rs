@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+
+use crate::constants::{ACTIVE_BET, AUTH_STATE, DELEGATION_STATUS_UNDELEGATED, PROFILE};
+use crate::constants::MAXIMUM_LIVE_PRICE_AGE_SECONDS;
+use crate::errors::BetError;
+use crate::state::{ActiveBet, BetTerms, UserAuthState, UserProfile};
+
+// Settles an expired prior bet (if still open) and opens the next one in a single
+// instruction, so traders can roll positions atomically. Base layer only, like
+// `open_bet_normal`. The prior bet settles on `settlement_price_feed` (a print published
+// after its expiry) and the new one opens on a live print from `pyth_price_feed`.
+#[derive(Accounts)]
+pub struct SettleAndReopenBet<'info> {
+    #[account(mut)]
+    pub user_signer: Signer<'info>,
+    #[account(
+        seeds = [AUTH_STATE, user_signer.key().as_ref()],
+        bump = user_auth_state.bump,
+        constraint = user_auth_state.user_authority == user_signer.key() @ BetError::UserProfileAuthorityMismatch,
+        constraint = user_auth_state.delegation_status == DELEGATION_STATUS_UNDELEGATED @ BetError::DelegatedUseQuickBet
+    )]
+    pub user_auth_state: Account<'info, UserAuthState>,
+    #[account(
+        mut,
+        seeds = [PROFILE, user_signer.key().as_ref()],
+        bump = user_profile.bump,
+        constraint = user_profile.authority == user_signer.key() @ BetError::UserProfileAuthorityMismatch
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+    #[account(
+        mut,
+        seeds = [ACTIVE_BET, user_signer.key().as_ref()],
+        bump = active_bet.bump,
+        constraint = active_bet.user == user_signer.key() @ BetError::UserProfileBetUserMismatch
+    )]
+    pub active_bet: Account<'info, ActiveBet>,
+    pub pyth_price_feed: Account<'info, PriceUpdateV2>,
+    // Required while the prior bet is still live.
+    pub settlement_price_feed: Option<Account<'info, PriceUpdateV2>>,
+}
+
+impl<'info> SettleAndReopenBet<'info> {
+    pub fn settle_and_reopen_bet(
+        &mut self,
+        asset_name_arg: String,
+        direction_arg: u8,
+        amount_arg: u64,
+        duration_seconds_arg: i64,
+        bet_terms_arg: BetTerms,
+    ) -> Result<()> {
+        let user_key = self.user_signer.key();
+        let user_profile = &mut self.user_profile;
+        let active_bet = &mut self.active_bet;
+
+        let order = crate::engine::BetOrder {
+            asset_name: asset_name_arg,
+            direction: direction_arg,
+            amount: amount_arg,
+            duration_seconds: duration_seconds_arg,
+            terms: bet_terms_arg,
+        };
+        crate::engine::validate_bet_order(&order)?;
+
+        let clock = Clock::get()?;
+        if active_bet.status == 0 {
+            let settlement_price_feed = self
+                .settlement_price_feed
+                .as_ref()
+                .ok_or(BetError::PythPriceFeedError)?;
+            let resolution_price = crate::read_settlement_price(
+                settlement_price_feed,
+                &active_bet.asset_name,
+                active_bet.expiry_timestamp,
+            )?;
+            crate::settle_expired_bet(active_bet, user_profile, resolution_price, clock.unix_timestamp)?;
+            msg!("Prior bet settled with status {}.", active_bet.status);
+        }
+
+        let initial_price = crate::read_oracle_price(
+            &self.pyth_price_feed,
+            &order.asset_name,
+            &clock,
+            MAXIMUM_LIVE_PRICE_AGE_SECONDS,
+        )?;
+
+        crate::place_bet(
+            active_bet,
+            user_profile,
+            user_key,
+            order,
+            initial_price,
+            clock.unix_timestamp,
+        )?;
+        Ok(())
+    }
+}
@@ -0,0 +1,95 @@
+use anchor_lang::prelude::*;
+
+use ephemeral_rollups_sdk::anchor::commit;
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+
+use crate::constants::{ACTIVE_BET, AUTH_STATE, CONFIG, DELEGATION_STATUS_DELEGATED, PROFILE};
+use crate::errors::BetError;
+use crate::state::{ActiveBet, ProgramConfig, UserAuthState, UserProfile};
+
+// `cancel_bet` for Quick Bets. Unlike resolving, only the bettor may cancel.
+#[commit]
+#[derive(Accounts)]
+#[instruction(user_authority_for_pdas: Pubkey)]
+pub struct CancelBetEphemeral<'info> {
+    #[account(
+        mut,
+        constraint = payer.key() == user_authority_for_pdas @ BetError::UserProfileAuthorityMismatch
+    )]
+    pub payer: Signer<'info>,
+    /// CHECK: delegated
+    #[account(mut, seeds = [AUTH_STATE, user_authority_for_pdas.as_ref()], bump)]
+    pub user_auth_state: AccountInfo<'info>,
+    /// CHECK: delegated
+    #[account(mut, seeds = [PROFILE, user_authority_for_pdas.as_ref()], bump)]
+    pub user_profile: AccountInfo<'info>,
+    /// CHECK: delegated
+    #[account(mut, seeds = [ACTIVE_BET, user_authority_for_pdas.as_ref()], bump)]
+    pub active_bet: AccountInfo<'info>,
+    #[account(seeds = [CONFIG], bump = program_config.bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+    // Must be the asset's push feed (`push_feed_address`).
+    pub pyth_price_feed: Account<'info, PriceUpdateV2>,
+}
+
+impl<'info> CancelBetEphemeral<'info> {
+    pub fn cancel_bet_ephemeral(&self, user_authority_for_pdas: Pubkey) -> Result<()> {
+        let mut auth_state: UserAuthState = {
+            let data = self.user_auth_state.try_borrow_data()?;
+            UserAuthState::try_deserialize(&mut &data[..])
+                .map_err(|_| error!(BetError::NotAuthenticatedOrDelegated))?
+        };
+        require!(
+            auth_state.delegation_status == DELEGATION_STATUS_DELEGATED,
+            BetError::NotDelegated
+        );
+        require!(
+            auth_state.user_authority == user_authority_for_pdas,
+            BetError::UserProfileAuthorityMismatch
+        );
+
+        let mut bet: ActiveBet = {
+            let data = self.active_bet.try_borrow_data()?;
+            ActiveBet::try_deserialize(&mut &data[..])
+                .map_err(|_| error!(BetError::BetNotActiveOrAlreadyResolved))?
+        };
+        let mut user_profile: UserProfile = {
+            let data = self.user_profile.try_borrow_data()?;
+            UserProfile::try_deserialize(&mut &data[..])
+                .map_err(|_| error!(BetError::UserProfileAuthorityMismatch))?
+        };
+        require!(
+            user_profile.authority == user_authority_for_pdas,
+            BetError::UserProfileAuthorityMismatch
+        );
+        require!(
+            bet.user == user_authority_for_pdas,
+            BetError::UserProfileBetUserMismatch
+        );
+
+        let clock = Clock::get()?;
+        crate::cancel_live_bet(
+            &mut bet,
+            &mut user_profile,
+            &self.pyth_price_feed,
+            self.program_config.cancel_window_seconds,
+            &clock,
+        )?;
+        auth_state.last_activity_timestamp = clock.unix_timestamp;
+
+        {
+            let mut data = self.user_profile.try_borrow_mut_data()?;
+            user_profile.try_serialize(&mut &mut data[..])?;
+        }
+        {
+            let mut data = self.active_bet.try_borrow_mut_data()?;
+            bet.try_serialize(&mut &mut data[..])?;
+        }
+        {
+            let mut data = self.user_auth_state.try_borrow_mut_data()?;
+            auth_state.try_serialize(&mut &mut data[..])?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+
+use ephemeral_rollups_sdk::anchor::commit;
+use ephemeral_rollups_sdk::ephem::{commit_accounts, commit_and_undelegate_accounts};
+
+use crate::constants::{CONFIG, MARKET};
+use crate::errors::BetError;
+use crate::state::{Market, ProgramConfig};
+
+// Runs on the ephemeral rollup: checkpoints a delegated market between scheduled commits.
+#[commit]
+#[derive(Accounts)]
+pub struct CommitMarket<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [MARKET, market.asset_name.as_bytes()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+}
+
+impl<'info> CommitMarket<'info> {
+    pub fn commit_market(&self) -> Result<()> {
+        commit_accounts(
+            &self.payer,
+            vec![&self.market.to_account_info()],
+            &self.magic_context,
+            &self.magic_program,
+        )?;
+        Ok(())
+    }
+}
+
+// Runs on the ephemeral rollup: admin returns the market to the base layer.
+#[commit]
+#[derive(Accounts)]
+pub struct UndelegateMarket<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [CONFIG],
+        bump = program_config.bump,
+        has_one = admin @ BetError::UnauthorizedAdmin
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+    #[account(
+        mut,
+        seeds = [MARKET, market.asset_name.as_bytes()],
+        bump = market.bump
+    )]
+    pub market: Account<'info, Market>,
+}
+
+impl<'info> UndelegateMarket<'info> {
+    pub fn undelegate_market(&self) -> Result<()> {
+        commit_and_undelegate_accounts(
+            &self.admin,
+            vec![&self.market.to_account_info()],
+            &self.magic_context,
+            &self.magic_program,
+        )?;
+        Ok(())
+    }
+}
@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+
+use ephemeral_rollups_sdk::anchor::commit;
+use ephemeral_rollups_sdk::ephem::commit_accounts;
+
+use crate::constants::{ACTIVE_BET, PROFILE};
+use crate::errors::BetError;
+use crate::state::{ActiveBet, UserProfile};
+
+// Runs on the ephemeral rollup: checkpoints the user's profile and bet to the base layer
+// while leaving them delegated.
+#[commit]
+#[derive(Accounts)]
+pub struct CommitUserState<'info> {
+    #[account(mut)]
+    pub user_authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [PROFILE, user_authority.key().as_ref()],
+        bump = user_profile.bump,
+        constraint = user_profile.authority == user_authority.key() @ BetError::UserProfileAuthorityMismatch
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+    #[account(
+        mut,
+        seeds = [ACTIVE_BET, user_authority.key().as_ref()],
+        bump = active_bet.bump
+    )]
+    pub active_bet: Account<'info, ActiveBet>,
+}
+
+impl<'info> CommitUserState<'info> {
+    pub fn commit_user_state(&self) -> Result<()> {
+        commit_accounts(
+            &self.user_authority,
+            vec![
+                &self.user_profile.to_account_info(),
+                &self.active_bet.to_account_info(),
+            ],
+            &self.magic_context,
+            &self.magic_program,
+        )?;
+        msg!("User state committed to the base layer.");
+        Ok(())
+    }
+}
+
+// Keeper variant: anyone may pay to checkpoint a delegated user's state.
+#[commit]
+#[derive(Accounts)]
+#[instruction(user_authority_for_pdas: Pubkey)]
+pub struct CommitUserStateFor<'info> {
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [PROFILE, user_authority_for_pdas.as_ref()],
+        bump = user_profile.bump,
+        constraint = user_profile.authority == user_authority_for_pdas @ BetError::UserProfileAuthorityMismatch
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+    #[account(
+        mut,
+        seeds = [ACTIVE_BET, user_authority_for_pdas.as_ref()],
+        bump = active_bet.bump
+    )]
+    pub active_bet: Account<'info, ActiveBet>,
+}
+
+impl<'info> CommitUserStateFor<'info> {
+    pub fn commit_user_state_for(&self, user_authority_for_pdas: Pubkey) -> Result<()> {
+        commit_accounts(
+            &self.keeper,
+            vec![
+                &self.user_profile.to_account_info(),
+                &self.active_bet.to_account_info(),
+            ],
+            &self.magic_context,
+            &self.magic_program,
+        )?;
+        msg!("State of {} committed to the base layer.", user_authority_for_pdas);
+        Ok(())
+    }
+}
@@ -1,9 +1,9 @@
 use anchor_lang::prelude::*;
 
 use ephemeral_rollups_sdk::anchor::delegate;
-use ephemeral_rollups_sdk::cpi::DelegateConfig;
 
-use crate::constants::ACTIVE_BET;
+use crate::constants::{ACTIVE_BET, AUTH_STATE, CONFIG};
+use crate::state::ProgramConfig;
 
 #[delegate]
 #[derive(Accounts)]
@@ -18,6 +18,12 @@ pub struct DelegateActiveBet<'info> {
         bump
     )]
     pub pda: AccountInfo<'info>,
+    /// CHECK: UserAuthState PDA holding the delegation settings; may already be delegated.
+    #[account(seeds = [AUTH_STATE, payer.key().as_ref()], bump)]
+    pub user_auth_state: AccountInfo<'info>,
+    // Optional, as in `DelegateAuthState`.
+    #[account(seeds = [CONFIG], bump = program_config.bump)]
+    pub program_config: Option<Account<'info, ProgramConfig>>,
 }
 
 impl<'info> DelegateActiveBet<'info> {
@@ -25,7 +31,10 @@ impl<'info> DelegateActiveBet<'info> {
         self.delegate_pda(
             &self.payer,
             &[ACTIVE_BET, self.payer.key().as_ref()],
-            DelegateConfig::default(),
+            crate::delegate_config_for(
+                &crate::read_user_auth_state(&self.user_auth_state)?,
+                self.program_config.as_deref(),
+            ),
         )?;
         Ok(())
     }
@@ -1,9 +1,9 @@
 use anchor_lang::prelude::*;
 
 use ephemeral_rollups_sdk::anchor::delegate;
-use ephemeral_rollups_sdk::cpi::DelegateConfig;
 
-use crate::constants::AUTH_STATE;
+use crate::constants::{AUTH_STATE, CONFIG, DELEGATION_STATUS_DELEGATED};
+use crate::state::ProgramConfig;
 
 #[delegate]
 #[derive(Accounts)]
@@ -18,14 +18,24 @@ pub struct DelegateAuthState<'info> {
         bump
     )]
     pub pda: AccountInfo<'info>,
+    // Optional: without a `ProgramConfig` only the user's preferences and the SDK defaults apply.
+    #[account(seeds = [CONFIG], bump = program_config.bump)]
+    pub program_config: Option<Account<'info, ProgramConfig>>,
 }
 
 impl<'info> DelegateAuthState<'info> {
     pub fn delegate_accounts(&self) -> Result<()> {
+        // Record the delegation deadline before ownership moves to the delegation program.
+        let mut auth_state = crate::read_user_auth_state(&self.pda)?;
+        auth_state.delegation_status = DELEGATION_STATUS_DELEGATED;
+        auth_state.delegation_expires_at =
+            crate::delegation_expiry(&auth_state, self.program_config.as_deref(), Clock::get()?.unix_timestamp)?;
+        auth_state.try_serialize(&mut &mut self.pda.try_borrow_mut_data()?[..])?;
+
         self.delegate_pda(
             &self.payer,
             &[AUTH_STATE, self.payer.key().as_ref()],
-            DelegateConfig::default(),
+            crate::delegate_config_for(&auth_state, self.program_config.as_deref()),
         )?;
         Ok(())
     }
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+
+use ephemeral_rollups_sdk::anchor::delegate;
+use ephemeral_rollups_sdk::cpi::DelegateConfig;
+
+use crate::constants::{CONFIG, MARKET};
+use crate::errors::BetError;
+use crate::state::ProgramConfig;
+
+// Admin moves a market PDA to the ephemeral rollup. The validator checkpoints it to the
+// base layer every `delegation_commit_frequency_ms`.
+#[delegate]
+#[derive(Accounts)]
+#[instruction(asset_name: String)]
+pub struct DelegateMarket<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [CONFIG],
+        bump = program_config.bump,
+        has_one = admin @ BetError::UnauthorizedAdmin
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+    /// CHECK: Market PDA to delegate.
+    #[account(
+        mut,
+        del,
+        seeds = [MARKET, asset_name.as_bytes()],
+        bump
+    )]
+    pub market: AccountInfo<'info>,
+}
+
+impl<'info> DelegateMarket<'info> {
+    pub fn delegate_accounts(&self, asset_name: String) -> Result<()> {
+        self.delegate_market(
+            &self.admin,
+            &[MARKET, asset_name.as_bytes()],
+            DelegateConfig {
+                commit_frequency_ms: self.program_config.delegation_commit_frequency_ms,
+                validator: self.program_config.delegation_validator,
+            },
+        )?;
+        Ok(())
+    }
+}
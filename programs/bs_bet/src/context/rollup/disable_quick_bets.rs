@@ -0,0 +1,74 @@
+use anchor_lang::prelude::*;
+
+use ephemeral_rollups_sdk::anchor::commit;
+use ephemeral_rollups_sdk::ephem::commit_and_undelegate_accounts;
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+
+use crate::constants::{ACTIVE_BET, AUTH_STATE, PROFILE};
+use crate::constants::{DELEGATION_STATUS_DELEGATED, DELEGATION_STATUS_UNDELEGATED};
+use crate::errors::BetError;
+use crate::state::{ActiveBet, UserAuthState, UserProfile};
+
+// Runs on the ephemeral rollup: resets the delegation status, then commits and undelegates all
+// three user PDAs so the flag lands on the base layer with the rest of the state.
+#[commit]
+#[derive(Accounts)]
+pub struct DisableQuickBets<'info> {
+    #[account(mut)]
+    pub user_authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [AUTH_STATE, user_authority.key().as_ref()],
+        bump = user_auth_state.bump,
+        constraint = user_auth_state.user_authority == user_authority.key() @ BetError::UserProfileAuthorityMismatch
+    )]
+    pub user_auth_state: Account<'info, UserAuthState>,
+    #[account(
+        mut,
+        seeds = [PROFILE, user_authority.key().as_ref()],
+        bump = user_profile.bump
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+    #[account(
+        mut,
+        seeds = [ACTIVE_BET, user_authority.key().as_ref()],
+        bump = active_bet.bump
+    )]
+    pub active_bet: Account<'info, ActiveBet>,
+    // Required only when the bet has expired unresolved; settles it before undelegating.
+    pub pyth_price_feed: Option<Account<'info, PriceUpdateV2>>,
+}
+
+impl<'info> DisableQuickBets<'info> {
+    pub fn disable_quick_bets(&mut self) -> Result<()> {
+        require!(
+            self.user_auth_state.delegation_status == DELEGATION_STATUS_DELEGATED,
+            BetError::NotDelegated
+        );
+        self.user_auth_state.delegation_status = DELEGATION_STATUS_UNDELEGATED;
+        crate::settle_before_undelegation(
+            &mut self.active_bet,
+            &mut self.user_profile,
+            self.pyth_price_feed.as_deref(),
+            &Clock::get()?,
+        )?;
+        // Flush the changes before the accounts are committed.
+        self.user_auth_state.exit(&crate::ID)?;
+        self.user_profile.exit(&crate::ID)?;
+        self.active_bet.exit(&crate::ID)?;
+
+        commit_and_undelegate_accounts(
+            &self.user_authority,
+            vec![
+                &self.user_auth_state.to_account_info(),
+                &self.user_profile.to_account_info(),
+                &self.active_bet.to_account_info(),
+            ],
+            &self.magic_context,
+            &self.magic_program,
+        )?;
+
+        msg!("Quick Bets disabled: state committed and undelegated.");
+        Ok(())
+    }
+}
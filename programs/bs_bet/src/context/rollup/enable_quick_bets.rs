@@ -0,0 +1,106 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as sysvar_instructions;
+
+use ephemeral_rollups_sdk::anchor::delegate;
+
+use crate::constants::{ACTIVE_BET, AUTH_STATE, CONFIG, DELEGATION_STATUS_DELEGATED, PROFILE};
+use crate::errors::BetError;
+use crate::state::{DelegationArgs, ProgramConfig, UserAuthState};
+
+// Verifies the signed message, marks the auth state delegated and delegates all three
+// user PDAs in one atomic call. Replaces `manage_delegation` + the three `delegate_*` calls.
+#[delegate]
+#[derive(Accounts)]
+#[instruction(user_signed_message: Vec<u8>, _signature: [u8; 64], delegation_args: DelegationArgs)]
+pub struct EnableQuickBets<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: UserAuthState PDA to delegate; deserialised in the handler.
+    #[account(
+        mut,
+        del,
+        seeds = [AUTH_STATE, payer.key().as_ref()],
+        bump
+    )]
+    pub auth_state: AccountInfo<'info>,
+    /// CHECK: UserProfile PDA to delegate.
+    #[account(
+        mut,
+        del,
+        seeds = [PROFILE, payer.key().as_ref()],
+        bump
+    )]
+    pub user_profile: AccountInfo<'info>,
+    /// CHECK: ActiveBet PDA to delegate.
+    #[account(
+        mut,
+        del,
+        seeds = [ACTIVE_BET, payer.key().as_ref()],
+        bump
+    )]
+    pub active_bet: AccountInfo<'info>,
+    #[account(address = sysvar_instructions::ID)]
+    /// CHECK: Instructions sysvar.
+    pub ix_sysvar: AccountInfo<'info>,
+    // Optional: without a `ProgramConfig` only the user's preferences and the SDK defaults apply.
+    #[account(seeds = [CONFIG], bump = program_config.bump)]
+    pub program_config: Option<Account<'info, ProgramConfig>>,
+}
+
+impl<'info> EnableQuickBets<'info> {
+    pub fn enable_quick_bets(
+        &self,
+        user_signed_message: Vec<u8>,
+        delegation_args: DelegationArgs,
+    ) -> Result<()> {
+        let user_key = self.payer.key();
+        let now = Clock::get()?.unix_timestamp;
+
+        // Auth state changes must be written before ownership moves to the delegation program.
+        let auth_state = {
+            let mut data = self.auth_state.try_borrow_mut_data()?;
+            let mut auth_state = UserAuthState::try_deserialize(&mut &data[..])?;
+            require!(
+                auth_state.user_authority == user_key,
+                BetError::UserProfileAuthorityMismatch
+            );
+            require!(
+                auth_state.delegation_status != DELEGATION_STATUS_DELEGATED,
+                BetError::AlreadyDelegated
+            );
+            crate::verify_delegation_message(&user_key, auth_state.nonce, &user_signed_message)?;
+
+            crate::apply_delegation_args(&mut auth_state, &delegation_args)?;
+
+            auth_state.delegation_status = DELEGATION_STATUS_DELEGATED;
+            auth_state.delegation_timestamp = now;
+            auth_state.delegation_expires_at =
+                crate::delegation_expiry(&auth_state, self.program_config.as_deref(), now)?;
+            auth_state.nonce = auth_state
+                .nonce
+                .checked_add(1)
+                .ok_or(BetError::TimestampOverflow)?;
+            auth_state.try_serialize(&mut &mut data[..])?;
+            auth_state
+        };
+
+        self.delegate_auth_state(
+            &self.payer,
+            &[AUTH_STATE, user_key.as_ref()],
+            crate::delegate_config_for(&auth_state, self.program_config.as_deref()),
+        )?;
+        self.delegate_user_profile(
+            &self.payer,
+            &[PROFILE, user_key.as_ref()],
+            crate::delegate_config_for(&auth_state, self.program_config.as_deref()),
+        )?;
+        self.delegate_active_bet(
+            &self.payer,
+            &[ACTIVE_BET, user_key.as_ref()],
+            crate::delegate_config_for(&auth_state, self.program_config.as_deref()),
+        )?;
+
+        msg!("Quick Bets enabled: auth state, profile and active bet delegated.");
+        Ok(())
+    }
+}
@@ -1,15 +1,33 @@
 pub mod manage_delegation;
+pub mod recover_delegation_state;
+pub mod set_delegation_preferences;
 pub mod delegate_auth_state;
 pub mod delegate_user_profile;
 pub mod delegate_active_bet;
+pub mod delegate_market;
+pub mod commit_market;
+pub mod enable_quick_bets;
+pub mod disable_quick_bets;
+pub mod commit_user_state;
 pub mod open_bet_ephemeral;
+pub mod cancel_bet_ephemeral;
 pub mod resolve_bet_ephemeral;
 pub mod undelegate_from_magicblock;
+pub mod undelegate_inactive_user;
 
 pub use manage_delegation::*;
+pub use recover_delegation_state::*;
+pub use set_delegation_preferences::*;
 pub use delegate_auth_state::*;
 pub use delegate_user_profile::*;
 pub use delegate_active_bet::*;
+pub use delegate_market::*;
+pub use commit_market::*;
+pub use enable_quick_bets::*;
+pub use disable_quick_bets::*;
+pub use commit_user_state::*;
 pub use open_bet_ephemeral::*;
+pub use cancel_bet_ephemeral::*;
 pub use resolve_bet_ephemeral::*;
 pub use undelegate_from_magicblock::*;
+pub use undelegate_inactive_user::*;
@@ -1,51 +1,64 @@
 use anchor_lang::prelude::*;
 
 use ephemeral_rollups_sdk::anchor::commit;
-use pyth_solana_receiver_sdk::price_update::get_feed_id_from_hex;
 use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
 
-use crate::constants::{MAXIMUM_PRICE_AGE_SECONDS, SOL_USD_FEED_ID_HEX};
+use crate::constants::{ACTIVE_BET, AUTH_STATE, MARKET, PROFILE};
+use crate::constants::{DELEGATION_STATUS_DELEGATED, MAXIMUM_LIVE_PRICE_AGE_SECONDS};
 use crate::errors::BetError;
-use crate::state::{ActiveBet, UserAuthState, UserProfile};
+use crate::state::{ActiveBet, BetTerms, Market, UserAuthState, UserProfile};
 
 #[commit]
 #[derive(Accounts)]
-#[instruction(asset_name_arg: String, direction_arg: u8, amount_arg: u64, duration_seconds_arg: i64, user_authority_for_pdas: Pubkey)]
+#[instruction(asset_name_arg: String, direction_arg: u8, amount_arg: u64, duration_seconds_arg: i64, bet_terms_arg: BetTerms, user_authority_for_pdas: Pubkey)]
 pub struct OpenBetEphemeral<'info> {
-    #[account(mut)]
+    // Only the bettor may stake their own points.
+    #[account(
+        mut,
+        constraint = payer.key() == user_authority_for_pdas @ BetError::UserProfileAuthorityMismatch
+    )]
     pub payer: Signer<'info>,
     /// CHECK: delegated
-    #[account(mut)]
+    #[account(mut, seeds = [AUTH_STATE, user_authority_for_pdas.as_ref()], bump)]
     pub user_auth_state: AccountInfo<'info>,
     /// CHECK: delegated
-    #[account(mut)]
+    #[account(mut, seeds = [PROFILE, user_authority_for_pdas.as_ref()], bump)]
     pub user_profile: AccountInfo<'info>,
     /// CHECK: delegated
-    #[account(mut)]
+    #[account(mut, seeds = [ACTIVE_BET, user_authority_for_pdas.as_ref()], bump)]
     pub active_bet: AccountInfo<'info>,
     pub pyth_price_feed: Account<'info, PriceUpdateV2>,
+    // Best-effort market stats (see `Market`); pass it when the market is delegated alongside the user.
+    #[account(mut, seeds = [MARKET, asset_name_arg.as_bytes()], bump = market_stats.bump)]
+    pub market_stats: Option<Account<'info, Market>>,
 }
 
 impl<'info> OpenBetEphemeral<'info> {
     pub fn open_bet_ephemeral(
-        &self,
+        &mut self,
         asset_name_arg: String,
         direction_arg: u8,
         amount_arg: u64,
         duration_seconds_arg: i64,
+        bet_terms_arg: BetTerms,
         user_authority_for_pdas: Pubkey,
     ) -> Result<()> {
-        require!(asset_name_arg == "SOL/USD", BetError::UnsupportedAsset);
-        require!(direction_arg == 0 || direction_arg == 1, BetError::InvalidDirection);
-        require!(amount_arg > 0, BetError::ZeroAmount);
-        require!(duration_seconds_arg > 0, BetError::InvalidDuration);
-
-        let auth_state: UserAuthState = {
+        let order = crate::engine::BetOrder {
+            asset_name: asset_name_arg,
+            direction: direction_arg,
+            amount: amount_arg,
+            duration_seconds: duration_seconds_arg,
+            terms: bet_terms_arg,
+        };
+        let mut auth_state: UserAuthState = {
             let data = self.user_auth_state.try_borrow_data()?;
             UserAuthState::try_deserialize(&mut &data[..])
                 .map_err(|_| error!(BetError::NotAuthenticatedOrDelegated))?
         };
-        require!(auth_state.is_delegated, BetError::NotDelegated);
+        require!(
+            auth_state.delegation_status == DELEGATION_STATUS_DELEGATED,
+            BetError::NotDelegated
+        );
         require!(
             auth_state.user_authority == user_authority_for_pdas,
             BetError::UserProfileAuthorityMismatch
@@ -60,7 +73,6 @@ impl<'info> OpenBetEphemeral<'info> {
             user_profile.authority == user_authority_for_pdas,
             BetError::UserProfileAuthorityMismatch
         );
-        require!(user_profile.points >= amount_arg, BetError::InsufficientPoints);
 
         let mut active_bet: ActiveBet = {
             let data = self.active_bet.try_borrow_data()?;
@@ -68,34 +80,31 @@ impl<'info> OpenBetEphemeral<'info> {
                 .map_err(|_| error!(BetError::BetNotActiveOrAlreadyResolved))?
         };
 
-        user_profile.points = user_profile
-            .points
-            .checked_sub(amount_arg)
-            .ok_or_else(|| error!(BetError::InsufficientPoints))?;
-
         let clock = Clock::get()?;
-        let target_feed_id =
-            get_feed_id_from_hex(SOL_USD_FEED_ID_HEX).map_err(|_| BetError::InvalidPythFeedIdFormat)?;
-        let current_pyth_price_struct = self
-            .pyth_price_feed
-            .get_price_no_older_than(&clock, MAXIMUM_PRICE_AGE_SECONDS, &target_feed_id)
-            .map_err(|_| BetError::PythPriceFeedError)?;
-        let initial_price = crate::scale_pyth_price_to_6_decimals(
-            current_pyth_price_struct.price,
-            current_pyth_price_struct.exponent,
+        require!(
+            auth_state.delegation_expires_at == 0
+                || clock.unix_timestamp < auth_state.delegation_expires_at,
+            BetError::DelegationExpired
+        );
+        let initial_price = crate::read_oracle_price(
+            &self.pyth_price_feed,
+            &order.asset_name,
+            &clock,
+            MAXIMUM_LIVE_PRICE_AGE_SECONDS,
         )?;
 
-        active_bet.user = user_authority_for_pdas;
-        active_bet.asset_name = asset_name_arg;
-        active_bet.initial_price = initial_price;
-        active_bet.expiry_timestamp = clock
-            .unix_timestamp
-            .checked_add(duration_seconds_arg)
-            .ok_or(BetError::TimestampOverflow)?;
-        active_bet.direction = direction_arg;
-        active_bet.amount_staked = amount_arg;
-        active_bet.resolved_price = 0;
-        active_bet.status = 0;
+        if let Some(market) = self.market_stats.as_mut() {
+            crate::record_market_bet(market, order.direction, order.amount)?;
+        }
+        crate::place_bet(
+            &mut active_bet,
+            &mut user_profile,
+            user_authority_for_pdas,
+            order,
+            initial_price,
+            clock.unix_timestamp,
+        )?;
+        auth_state.last_activity_timestamp = clock.unix_timestamp;
 
         {
             let mut data = self.user_profile.try_borrow_mut_data()?;
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{ACTIVE_BET, AUTH_STATE, PROFILE};
+use crate::constants::DELEGATION_STATUS_UNDELEGATED;
+use crate::errors::BetError;
+use crate::state::UserAuthState;
+
+// Base layer: resets a delegation status that no longer matches reality, e.g. a
+// `manage_delegation(1)` whose SDK delegation never landed, or an undelegation that
+// skipped `manage_delegation(0)`. Only allowed while all three PDAs are owned by this
+// program, i.e. none of them is held by the delegation program.
+#[derive(Accounts)]
+pub struct RecoverDelegationState<'info> {
+    pub user_authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [AUTH_STATE, user_authority.key().as_ref()],
+        bump = user_auth_state.bump,
+        constraint = user_auth_state.user_authority == user_authority.key() @ BetError::UserProfileAuthorityMismatch
+    )]
+    pub user_auth_state: Account<'info, UserAuthState>,
+    /// CHECK: Only the owner is inspected.
+    #[account(seeds = [PROFILE, user_authority.key().as_ref()], bump)]
+    pub user_profile: AccountInfo<'info>,
+    /// CHECK: Only the owner is inspected.
+    #[account(seeds = [ACTIVE_BET, user_authority.key().as_ref()], bump)]
+    pub active_bet: AccountInfo<'info>,
+}
+
+impl<'info> RecoverDelegationState<'info> {
+    pub fn recover_delegation_state(&mut self) -> Result<()> {
+        for account in [
+            self.user_auth_state.to_account_info(),
+            self.user_profile.to_account_info(),
+            self.active_bet.to_account_info(),
+        ] {
+            require_keys_eq!(*account.owner, crate::ID, BetError::AlreadyDelegated);
+        }
+
+        let auth_state = &mut self.user_auth_state;
+        require!(
+            auth_state.delegation_status != DELEGATION_STATUS_UNDELEGATED,
+            BetError::NothingToRecover
+        );
+        msg!(
+            "Recovering delegation status {} -> undelegated.",
+            auth_state.delegation_status
+        );
+        auth_state.delegation_status = DELEGATION_STATUS_UNDELEGATED;
+        Ok(())
+    }
+}
@@ -1,10 +1,9 @@
 use anchor_lang::prelude::*;
 
 use ephemeral_rollups_sdk::anchor::commit;
-use pyth_solana_receiver_sdk::price_update::get_feed_id_from_hex;
 use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
 
-use crate::constants::{MAXIMUM_PRICE_AGE_SECONDS, SOL_USD_FEED_ID_HEX};
+use crate::constants::{ACTIVE_BET, AUTH_STATE, DELEGATION_STATUS_DELEGATED, PROFILE};
 use crate::errors::BetError;
 use crate::state::{ActiveBet, UserAuthState, UserProfile};
 
@@ -15,26 +14,30 @@ pub struct ResolveBetEphemeral<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
     /// CHECK: delegated
-    #[account(mut)]
+    #[account(mut, seeds = [AUTH_STATE, user_authority_for_pdas.as_ref()], bump)]
     pub user_auth_state: AccountInfo<'info>,
     /// CHECK: delegated
-    #[account(mut)]
+    #[account(mut, seeds = [PROFILE, user_authority_for_pdas.as_ref()], bump)]
     pub user_profile: AccountInfo<'info>,
     /// CHECK: delegated
-    #[account(mut)]
+    #[account(mut, seeds = [ACTIVE_BET, user_authority_for_pdas.as_ref()], bump)]
     pub active_bet: AccountInfo<'info>,
-    pub pyth_price_feed: Account<'info, PriceUpdateV2>,
+    // Omit only to void a bet nobody settled within `PRICE_OUTAGE_VOID_SECONDS`.
+    pub pyth_price_feed: Option<Account<'info, PriceUpdateV2>>,
     pub clock: Sysvar<'info, Clock>,
 }
 
 impl<'info> ResolveBetEphemeral<'info> {
     pub fn resolve_bet_ephemeral(&self, user_authority_for_pdas: Pubkey) -> Result<()> {
-        let auth_state: UserAuthState = {
+        let mut auth_state: UserAuthState = {
             let data = self.user_auth_state.try_borrow_data()?;
             UserAuthState::try_deserialize(&mut &data[..])
                 .map_err(|_| error!(BetError::NotAuthenticatedOrDelegated))?
         };
-        require!(auth_state.is_delegated, BetError::NotDelegated);
+        require!(
+            auth_state.delegation_status == DELEGATION_STATUS_DELEGATED,
+            BetError::NotDelegated
+        );
         require!(
             auth_state.user_authority == user_authority_for_pdas,
             BetError::UserProfileAuthorityMismatch
@@ -50,41 +53,24 @@ impl<'info> ResolveBetEphemeral<'info> {
             UserProfile::try_deserialize(&mut &data[..])
                 .map_err(|_| error!(BetError::UserProfileAuthorityMismatch))?
         };
+        // Same ownership rules as the seeds and constraints of `resolve_bet_normal`.
+        require!(
+            user_profile.authority == user_authority_for_pdas,
+            BetError::UserProfileAuthorityMismatch
+        );
+        require!(
+            bet.user == user_authority_for_pdas,
+            BetError::UserProfileBetUserMismatch
+        );
 
-        require!(bet.status == 0, BetError::BetNotActiveOrAlreadyResolved);
         let clock = &self.clock;
-        require!(clock.unix_timestamp > bet.expiry_timestamp, BetError::BetNotYetExpired);
-
-        let target_feed_id =
-            get_feed_id_from_hex(SOL_USD_FEED_ID_HEX).map_err(|_| BetError::InvalidPythFeedIdFormat)?;
-        let resolved_price_struct = self
-            .pyth_price_feed
-            .get_price_no_older_than(clock, MAXIMUM_PRICE_AGE_SECONDS, &target_feed_id)
-            .map_err(|_| BetError::PythPriceFeedError)?;
-        bet.resolved_price = crate::scale_pyth_price_to_6_decimals(
-            resolved_price_struct.price,
-            resolved_price_struct.exponent,
+        crate::settle_or_void_expired_bet(
+            &mut bet,
+            &mut user_profile,
+            self.pyth_price_feed.as_deref(),
+            clock,
         )?;
-
-        let won = if bet.direction == 1 {
-            bet.resolved_price > bet.initial_price
-        } else {
-            bet.resolved_price < bet.initial_price
-        };
-
-        if won {
-            bet.status = 1;
-            let payout = bet
-                .amount_staked
-                .checked_mul(2)
-                .ok_or(BetError::PriceCalculationOverflow)?;
-            user_profile.points = user_profile
-                .points
-                .checked_add(payout)
-                .ok_or(BetError::PriceCalculationOverflow)?;
-        } else {
-            bet.status = 2;
-        }
+        auth_state.last_activity_timestamp = clock.unix_timestamp;
 
         {
             let mut data = self.user_profile.try_borrow_mut_data()?;
@@ -94,6 +80,10 @@ impl<'info> ResolveBetEphemeral<'info> {
             let mut data = self.active_bet.try_borrow_mut_data()?;
             bet.try_serialize(&mut &mut data[..])?;
         }
+        {
+            let mut data = self.user_auth_state.try_borrow_mut_data()?;
+            auth_state.try_serialize(&mut &mut data[..])?;
+        }
 
         Ok(())
     }
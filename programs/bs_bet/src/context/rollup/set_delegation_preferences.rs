@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::AUTH_STATE;
+use crate::errors::BetError;
+use crate::state::{DelegationArgs, UserAuthState};
+
+// Base layer only: delegation settings are read when the auth state is next delegated.
+#[derive(Accounts)]
+pub struct SetDelegationPreferences<'info> {
+    pub user_authority: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [AUTH_STATE, user_authority.key().as_ref()],
+        bump = user_auth_state.bump,
+        constraint = user_auth_state.user_authority == user_authority.key() @ BetError::UserProfileAuthorityMismatch
+    )]
+    pub user_auth_state: Account<'info, UserAuthState>,
+}
+
+impl<'info> SetDelegationPreferences<'info> {
+    pub fn set_delegation_preferences(&mut self, delegation_args: DelegationArgs) -> Result<()> {
+        crate::apply_delegation_args(&mut self.user_auth_state, &delegation_args)
+    }
+}
@@ -1,6 +1,10 @@
 use anchor_lang::prelude::*;
 
 use ephemeral_rollups_sdk::anchor::commit;
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+
+use crate::errors::BetError;
+use crate::state::{ActiveBet, UserProfile};
 
 #[commit]
 #[derive(Accounts)]
@@ -18,6 +22,8 @@ pub struct UndelegateFromMagicBlock<'info> {
     /// CHECK: delegated
     #[account(mut)]
     pub active_bet_to_undelegate: AccountInfo<'info>,
+    // Required only when the bet has expired unresolved; settles it before undelegating.
+    pub pyth_price_feed: Option<Account<'info, PriceUpdateV2>>,
 
     #[account(executable)]
     /// CHECK: MagicBlock magic program (required by `commit_and_undelegate_accounts`).
@@ -26,3 +32,45 @@ pub struct UndelegateFromMagicBlock<'info> {
     /// CHECK: MagicBlock context account (required by `commit_and_undelegate_accounts`).
     pub magic_context: AccountInfo<'info>,
 }
+
+impl<'info> UndelegateFromMagicBlock<'info> {
+    // Leaves nothing in limbo: expired bets are settled (or voided) on the rollup
+    // before the accounts are committed back.
+    pub fn settle_expired_bet(&self) -> Result<()> {
+        let mut user_profile: UserProfile = {
+            let data = self.user_profile_to_undelegate.try_borrow_data()?;
+            UserProfile::try_deserialize(&mut &data[..])
+                .map_err(|_| error!(BetError::UserProfileAuthorityMismatch))?
+        };
+        let mut bet: ActiveBet = {
+            let data = self.active_bet_to_undelegate.try_borrow_data()?;
+            ActiveBet::try_deserialize(&mut &data[..])
+                .map_err(|_| error!(BetError::BetNotActiveOrAlreadyResolved))?
+        };
+        require!(
+            user_profile.authority == self.user_authority.key(),
+            BetError::UserProfileAuthorityMismatch
+        );
+        require!(
+            bet.user == self.user_authority.key(),
+            BetError::UserProfileBetUserMismatch
+        );
+
+        crate::settle_before_undelegation(
+            &mut bet,
+            &mut user_profile,
+            self.pyth_price_feed.as_deref(),
+            &Clock::get()?,
+        )?;
+
+        {
+            let mut data = self.user_profile_to_undelegate.try_borrow_mut_data()?;
+            user_profile.try_serialize(&mut &mut data[..])?;
+        }
+        {
+            let mut data = self.active_bet_to_undelegate.try_borrow_mut_data()?;
+            bet.try_serialize(&mut &mut data[..])?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+
+use ephemeral_rollups_sdk::anchor::commit;
+use ephemeral_rollups_sdk::ephem::commit_and_undelegate_accounts;
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+
+use crate::constants::{ACTIVE_BET, AUTH_STATE, CONFIG, PROFILE};
+use crate::constants::{DELEGATION_STATUS_DELEGATED, DELEGATION_STATUS_UNDELEGATED};
+use crate::errors::BetError;
+use crate::state::{ActiveBet, ProgramConfig, UserAuthState, UserProfile};
+
+// Keeper crank on the ephemeral rollup: once a delegated user has been idle past the
+// configured timeout (or their delegation lifetime has run out), commit and undelegate
+// their accounts so the base-layer flows work again.
+#[commit]
+#[derive(Accounts)]
+#[instruction(user_authority_for_pdas: Pubkey)]
+pub struct UndelegateInactiveUser<'info> {
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [AUTH_STATE, user_authority_for_pdas.as_ref()],
+        bump = user_auth_state.bump,
+        constraint = user_auth_state.user_authority == user_authority_for_pdas @ BetError::UserProfileAuthorityMismatch
+    )]
+    pub user_auth_state: Account<'info, UserAuthState>,
+    #[account(
+        mut,
+        seeds = [PROFILE, user_authority_for_pdas.as_ref()],
+        bump = user_profile.bump
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+    #[account(
+        mut,
+        seeds = [ACTIVE_BET, user_authority_for_pdas.as_ref()],
+        bump = active_bet.bump
+    )]
+    pub active_bet: Account<'info, ActiveBet>,
+    // Required only when the bet has expired unresolved; settles it before undelegating.
+    pub pyth_price_feed: Option<Account<'info, PriceUpdateV2>>,
+    #[account(seeds = [CONFIG], bump = program_config.bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+}
+
+impl<'info> UndelegateInactiveUser<'info> {
+    pub fn undelegate_inactive_user(&mut self, user_authority_for_pdas: Pubkey) -> Result<()> {
+        let auth_state = &mut self.user_auth_state;
+        let timeout = self.program_config.inactivity_timeout_seconds;
+        let clock = Clock::get()?;
+        let now = clock.unix_timestamp;
+
+        require!(
+            auth_state.delegation_status == DELEGATION_STATUS_DELEGATED,
+            BetError::NotDelegated
+        );
+        let last_activity = auth_state
+            .last_activity_timestamp
+            .max(auth_state.delegation_timestamp);
+        let idle = timeout > 0
+            && now
+                >= last_activity
+                    .checked_add(timeout)
+                    .ok_or(BetError::TimestampOverflow)?;
+        let expired = auth_state.delegation_expires_at != 0 && now >= auth_state.delegation_expires_at;
+        require!(idle || expired, BetError::UserStillActive);
+
+        auth_state.delegation_status = DELEGATION_STATUS_UNDELEGATED;
+        crate::settle_before_undelegation(
+            &mut self.active_bet,
+            &mut self.user_profile,
+            self.pyth_price_feed.as_deref(),
+            &clock,
+        )?;
+        // Flush the changes before the accounts are committed.
+        self.user_auth_state.exit(&crate::ID)?;
+        self.user_profile.exit(&crate::ID)?;
+        self.active_bet.exit(&crate::ID)?;
+
+        commit_and_undelegate_accounts(
+            &self.keeper,
+            vec![
+                &self.user_auth_state.to_account_info(),
+                &self.user_profile.to_account_info(),
+                &self.active_bet.to_account_info(),
+            ],
+            &self.magic_context,
+            &self.magic_program,
+        )?;
+
+        msg!("Inactive user {} undelegated.", user_authority_for_pdas);
+        Ok(())
+    }
+}
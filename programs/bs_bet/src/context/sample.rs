@@ -0,0 +1,107 @@
+use anchor_lang::prelude::*;
+
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+use pyth_solana_receiver_sdk::price_update::get_feed_id_from_hex;
+
+use crate::constants::{ACTIVE_BET, PROFILE};
+use crate::constants::{BET_TYPE_ASIAN, BET_TYPE_NO_TOUCH, BET_TYPE_TOUCH};
+use crate::constants::MAXIMUM_LIVE_PRICE_AGE_SECONDS;
+use crate::errors::BetError;
+use crate::state::{ActiveBet, UserProfile};
+
+// Permissionless crank: any keeper may push an oracle observation into a live bet.
+// Asian bets accumulate it into their average; touch / no-touch bets settle early
+// once the barrier is hit. Works on the base layer and, for delegated bets, on the
+// ephemeral rollup.
+#[derive(Accounts)]
+#[instruction(user_authority_for_pdas: Pubkey)]
+pub struct RecordPriceSample<'info> {
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [ACTIVE_BET, user_authority_for_pdas.as_ref()],
+        bump = active_bet.bump,
+        constraint = active_bet.user == user_authority_for_pdas @ BetError::UserProfileBetUserMismatch
+    )]
+    pub active_bet: Account<'info, ActiveBet>,
+    #[account(
+        mut,
+        seeds = [PROFILE, user_authority_for_pdas.as_ref()],
+        bump = user_profile.bump,
+        constraint = user_profile.authority == user_authority_for_pdas @ BetError::UserProfileAuthorityMismatch
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+    pub pyth_price_feed: Account<'info, PriceUpdateV2>,
+}
+
+impl<'info> RecordPriceSample<'info> {
+    pub fn record_price_sample(&mut self, _user_authority_for_pdas: Pubkey) -> Result<()> {
+        let bet = &mut self.active_bet;
+        let clock = Clock::get()?;
+
+        require!(bet.status == 0, BetError::BetNotActiveOrAlreadyResolved);
+        require!(
+            bet.bet_type == BET_TYPE_ASIAN
+                || bet.bet_type == BET_TYPE_TOUCH
+                || bet.bet_type == BET_TYPE_NO_TOUCH,
+            BetError::InvalidBetType
+        );
+        // Asian slots are bounded by publish time instead, so a keeper that lands just after
+        // expiry can still fill the last slot before the bet is settled.
+        if bet.bet_type != BET_TYPE_ASIAN {
+            require!(
+                clock.unix_timestamp <= bet.expiry_timestamp,
+                BetError::BetAlreadyExpired
+            );
+        }
+
+        let target_feed_id = get_feed_id_from_hex(crate::feed_id_hex_for_asset(&bet.asset_name)?)
+            .map_err(|_| BetError::InvalidPythFeedIdFormat)?;
+        let sample_price_struct = self
+            .pyth_price_feed
+            .get_price_no_older_than(&clock, MAXIMUM_LIVE_PRICE_AGE_SECONDS, &target_feed_id)
+            .map_err(|_| BetError::PythPriceTooOldOrUnavailable)?;
+        // Prints from before the bet opened say nothing about its path.
+        require!(
+            sample_price_struct.publish_time >= bet.opened_at,
+            BetError::PythPriceTooOldOrUnavailable
+        );
+        let sample_price = crate::scale_pyth_price_to_6_decimals(
+            sample_price_struct.price,
+            sample_price_struct.exponent,
+        )?;
+
+        if bet.bet_type == BET_TYPE_ASIAN {
+            crate::engine::record_asian_sample(bet, sample_price, sample_price_struct.publish_time)?;
+            msg!(
+                "Price sample {} recorded: {} (running sum {})",
+                bet.sample_count,
+                sample_price,
+                bet.sample_price_sum
+            );
+            return Ok(());
+        }
+
+        bet.last_sample_timestamp = clock.unix_timestamp;
+        if !crate::engine::barrier_touched(bet, sample_price) {
+            return Ok(());
+        }
+
+        bet.resolved_price = sample_price;
+        let user_profile = &mut self.user_profile;
+        if bet.bet_type == BET_TYPE_TOUCH {
+            bet.status = 1;
+            let payout = crate::engine::bet_payout(bet)?;
+            user_profile.points = user_profile
+                .points
+                .checked_add(payout)
+                .ok_or(BetError::PriceCalculationOverflow)?;
+        } else {
+            bet.status = 2;
+        }
+        crate::record_bet_outcome(user_profile, bet.status == 1);
+        msg!("Barrier {} touched at {}; bet settled early.", bet.barrier_price, sample_price);
+        Ok(())
+    }
+}
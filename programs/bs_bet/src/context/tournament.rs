@@ -0,0 +1,410 @@
+use anchor_lang::prelude::*;
+
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
+
+use crate::constants::{CONFIG, PROFILE, TOURNAMENT, TOURNAMENT_ENTRY};
+use crate::constants::{BPS_DENOMINATOR, MAXIMUM_LIVE_PRICE_AGE_SECONDS, PRICE_OUTAGE_VOID_SECONDS, MAX_TOURNAMENT_ENTRANTS, MAX_TOURNAMENT_PAYOUT_PLACES, TOURNAMENT_ASSET};
+use crate::errors::BetError;
+use crate::state::{ProgramConfig, Tournament, TournamentArgs, TournamentEntry, UserProfile};
+use crate::state::{TOURNAMENT_ENTRY_SPACE, TOURNAMENT_SPACE};
+
+#[derive(Accounts)]
+#[instruction(tournament_id: u64)]
+pub struct CreateTournament<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    #[account(
+        seeds = [CONFIG],
+        bump = program_config.bump,
+        has_one = admin @ BetError::UnauthorizedAdmin
+    )]
+    pub program_config: Account<'info, ProgramConfig>,
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + TOURNAMENT_SPACE,
+        seeds = [TOURNAMENT, &tournament_id.to_le_bytes()],
+        bump
+    )]
+    pub tournament: Account<'info, Tournament>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> CreateTournament<'info> {
+    pub fn create_tournament(&mut self, tournament_id: u64, args: TournamentArgs, bump: u8) -> Result<()> {
+        require!(
+            args.start_timestamp < args.end_timestamp,
+            BetError::InvalidTournament
+        );
+        // Settlement needs every entry in one transaction, which caps the field size.
+        require!(
+            args.starting_points > 0
+                && args.max_entrants > 0
+                && args.max_entrants <= MAX_TOURNAMENT_ENTRANTS,
+            BetError::InvalidTournament
+        );
+        require!(
+            !args.payout_table_bps.is_empty()
+                && args.payout_table_bps.len() <= MAX_TOURNAMENT_PAYOUT_PLACES,
+            BetError::InvalidTournament
+        );
+        let total_bps: u64 = args.payout_table_bps.iter().map(|bps| *bps as u64).sum();
+        require!(total_bps == BPS_DENOMINATOR, BetError::InvalidTournament);
+
+        let tournament = &mut self.tournament;
+        tournament.tournament_id = tournament_id;
+        tournament.admin = self.admin.key();
+        tournament.start_timestamp = args.start_timestamp;
+        tournament.end_timestamp = args.end_timestamp;
+        tournament.entry_fee = args.entry_fee;
+        tournament.starting_points = args.starting_points;
+        tournament.max_entrants = args.max_entrants;
+        tournament.entrant_count = 0;
+        tournament.prize_pool = 0;
+        tournament.payout_table_bps = args.payout_table_bps;
+        tournament.settled = false;
+        tournament.bump = bump;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct JoinTournament<'info> {
+    #[account(mut)]
+    pub user_signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [PROFILE, user_signer.key().as_ref()],
+        bump = user_profile.bump,
+        constraint = user_profile.authority == user_signer.key() @ BetError::UserProfileAuthorityMismatch
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+    #[account(
+        mut,
+        seeds = [TOURNAMENT, &tournament.tournament_id.to_le_bytes()],
+        bump = tournament.bump
+    )]
+    pub tournament: Account<'info, Tournament>,
+    #[account(
+        init,
+        payer = user_signer,
+        space = 8 + TOURNAMENT_ENTRY_SPACE,
+        seeds = [TOURNAMENT_ENTRY, tournament.key().as_ref(), user_signer.key().as_ref()],
+        bump
+    )]
+    pub tournament_entry: Account<'info, TournamentEntry>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> JoinTournament<'info> {
+    pub fn join_tournament(&mut self, bump: u8) -> Result<()> {
+        let tournament = &mut self.tournament;
+        let user_profile = &mut self.user_profile;
+        let clock = Clock::get()?;
+
+        require!(
+            clock.unix_timestamp < tournament.end_timestamp,
+            BetError::TournamentNotRunning
+        );
+        require!(
+            tournament.entrant_count < tournament.max_entrants,
+            BetError::TournamentFull
+        );
+
+        user_profile.points = user_profile
+            .points
+            .checked_sub(tournament.entry_fee)
+            .ok_or_else(|| error!(BetError::InsufficientPoints))?;
+        tournament.prize_pool = tournament
+            .prize_pool
+            .checked_add(tournament.entry_fee)
+            .ok_or(BetError::PriceCalculationOverflow)?;
+        tournament.entrant_count = tournament
+            .entrant_count
+            .checked_add(1)
+            .ok_or(BetError::PriceCalculationOverflow)?;
+
+        let entry = &mut self.tournament_entry;
+        entry.tournament = tournament.key();
+        entry.user = self.user_signer.key();
+        entry.points = tournament.starting_points;
+        entry.bet_status = 2; // no bet yet
+        entry.rank = 0;
+        entry.prize = 0;
+        entry.prize_claimed = false;
+        entry.bump = bump;
+        Ok(())
+    }
+}
+
+// Tournament bets are SOL/USD up/down at even money and only move the tournament balance.
+#[derive(Accounts)]
+pub struct OpenTournamentBet<'info> {
+    #[account(mut)]
+    pub user_signer: Signer<'info>,
+    #[account(
+        seeds = [TOURNAMENT, &tournament.tournament_id.to_le_bytes()],
+        bump = tournament.bump
+    )]
+    pub tournament: Account<'info, Tournament>,
+    #[account(
+        mut,
+        seeds = [TOURNAMENT_ENTRY, tournament.key().as_ref(), user_signer.key().as_ref()],
+        bump = tournament_entry.bump
+    )]
+    pub tournament_entry: Account<'info, TournamentEntry>,
+    pub pyth_price_feed: Account<'info, PriceUpdateV2>,
+}
+
+impl<'info> OpenTournamentBet<'info> {
+    pub fn open_tournament_bet(
+        &mut self,
+        direction_arg: u8,
+        amount_arg: u64,
+        duration_seconds_arg: i64,
+    ) -> Result<()> {
+        let tournament = &self.tournament;
+        let entry = &mut self.tournament_entry;
+        let clock = Clock::get()?;
+
+        require!(direction_arg == 0 || direction_arg == 1, BetError::InvalidDirection);
+        require!(amount_arg > 0, BetError::ZeroAmount);
+        require!(duration_seconds_arg > 0, BetError::InvalidDuration);
+        require!(
+            clock.unix_timestamp >= tournament.start_timestamp
+                && clock.unix_timestamp < tournament.end_timestamp,
+            BetError::TournamentNotRunning
+        );
+        require!(entry.bet_status != 0, BetError::BetStillActive);
+
+        let expiry_timestamp = clock
+            .unix_timestamp
+            .checked_add(duration_seconds_arg)
+            .ok_or(BetError::TimestampOverflow)?;
+        require!(
+            expiry_timestamp <= tournament.end_timestamp,
+            BetError::InvalidDuration
+        );
+
+        entry.points = entry
+            .points
+            .checked_sub(amount_arg)
+            .ok_or_else(|| error!(BetError::InsufficientPoints))?;
+
+        entry.bet_direction = direction_arg;
+        entry.bet_amount = amount_arg;
+        entry.bet_initial_price = crate::read_oracle_price(
+            &self.pyth_price_feed,
+            TOURNAMENT_ASSET,
+            &clock,
+            MAXIMUM_LIVE_PRICE_AGE_SECONDS,
+        )?;
+        entry.bet_expiry_timestamp = expiry_timestamp;
+        entry.bet_status = 0;
+        Ok(())
+    }
+}
+
+// Permissionless so that no entrant can hold up settlement by leaving a bet open.
+#[derive(Accounts)]
+pub struct ResolveTournamentBet<'info> {
+    #[account(mut)]
+    pub resolver: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [TOURNAMENT_ENTRY, tournament_entry.tournament.as_ref(), tournament_entry.user.as_ref()],
+        bump = tournament_entry.bump
+    )]
+    pub tournament_entry: Account<'info, TournamentEntry>,
+    // Omit only to void a bet nobody settled within `PRICE_OUTAGE_VOID_SECONDS`;
+    // otherwise a missing print would block `settle_tournament` for good.
+    pub pyth_price_feed: Option<Account<'info, PriceUpdateV2>>,
+}
+
+impl<'info> ResolveTournamentBet<'info> {
+    pub fn resolve_tournament_bet(&mut self) -> Result<()> {
+        let entry = &mut self.tournament_entry;
+        let clock = Clock::get()?;
+
+        require!(entry.bet_status == 0, BetError::BetNotActiveOrAlreadyResolved);
+        require!(
+            clock.unix_timestamp > entry.bet_expiry_timestamp,
+            BetError::BetNotYetExpired
+        );
+
+        let Some(pyth_price_feed) = self.pyth_price_feed.as_ref() else {
+            let void_after = entry
+                .bet_expiry_timestamp
+                .checked_add(PRICE_OUTAGE_VOID_SECONDS)
+                .ok_or(BetError::TimestampOverflow)?;
+            require!(clock.unix_timestamp > void_after, BetError::SettlementPriceMissing);
+            entry.points = entry
+                .points
+                .checked_add(entry.bet_amount)
+                .ok_or(BetError::PriceCalculationOverflow)?;
+            entry.bet_status = 2;
+            msg!("Tournament bet voided; no settlement print was supplied.");
+            return Ok(());
+        };
+
+        // Anyone may resolve, so only a print from after expiry can decide the bet.
+        let resolved_price = crate::read_settlement_price(
+            pyth_price_feed,
+            TOURNAMENT_ASSET,
+            entry.bet_expiry_timestamp,
+        )?;
+
+        let won = if entry.bet_direction == 1 {
+            resolved_price > entry.bet_initial_price
+        } else {
+            resolved_price < entry.bet_initial_price
+        };
+
+        if won {
+            entry.bet_status = 1;
+            let payout = entry
+                .bet_amount
+                .checked_mul(2)
+                .ok_or(BetError::PriceCalculationOverflow)?;
+            entry.points = entry
+                .points
+                .checked_add(payout)
+                .ok_or(BetError::PriceCalculationOverflow)?;
+        } else {
+            entry.bet_status = 2;
+        }
+        Ok(())
+    }
+}
+
+// Permissionless after the end: ranks all entries (passed as writable remaining
+// accounts) by tournament points and records each entrant's prize. Every entry must be
+// passed exactly once (checked against `entrant_count`) and have no open bet.
+#[derive(Accounts)]
+pub struct SettleTournament<'info> {
+    #[account(mut)]
+    pub settler: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [TOURNAMENT, &tournament.tournament_id.to_le_bytes()],
+        bump = tournament.bump
+    )]
+    pub tournament: Account<'info, Tournament>,
+}
+
+impl<'info> SettleTournament<'info> {
+    pub fn settle_tournament(&mut self, entry_accounts: &[AccountInfo<'info>]) -> Result<()> {
+        let tournament = &mut self.tournament;
+        let tournament_key = tournament.key();
+        let clock = Clock::get()?;
+
+        require!(!tournament.settled, BetError::TournamentAlreadySettled);
+        require!(
+            clock.unix_timestamp >= tournament.end_timestamp,
+            BetError::TournamentNotEnded
+        );
+        require!(
+            entry_accounts.len() == tournament.entrant_count as usize,
+            BetError::TournamentEntriesMismatch
+        );
+
+        let mut entries: Vec<(usize, TournamentEntry)> = Vec::with_capacity(entry_accounts.len());
+        for (i, entry_account) in entry_accounts.iter().enumerate() {
+            require_keys_eq!(*entry_account.owner, crate::ID, BetError::TournamentEntriesMismatch);
+            require!(
+                entry_accounts[..i].iter().all(|other| other.key != entry_account.key),
+                BetError::TournamentEntriesMismatch
+            );
+            let entry = {
+                let data = entry_account.try_borrow_data()?;
+                TournamentEntry::try_deserialize(&mut &data[..])
+                    .map_err(|_| error!(BetError::TournamentEntriesMismatch))?
+            };
+            require_keys_eq!(entry.tournament, tournament_key, BetError::TournamentEntriesMismatch);
+            let expected_key = Pubkey::create_program_address(
+                &[TOURNAMENT_ENTRY, tournament_key.as_ref(), entry.user.as_ref(), &[entry.bump]],
+                &crate::ID,
+            )
+            .map_err(|_| error!(BetError::TournamentEntriesMismatch))?;
+            require_keys_eq!(*entry_account.key, expected_key, BetError::TournamentEntriesMismatch);
+            require!(entry.bet_status != 0, BetError::TournamentEntriesMismatch);
+            entries.push((i, entry));
+        }
+
+        // Highest balance first; ties go to the lower user key for determinism.
+        entries.sort_by(|(_, a), (_, b)| b.points.cmp(&a.points).then(a.user.cmp(&b.user)));
+
+        let paid_places = tournament.payout_table_bps.len().min(entries.len());
+        let mut distributed: u64 = 0;
+        for (rank, (_, entry)) in entries.iter_mut().enumerate() {
+            entry.rank = rank as u32 + 1;
+            if rank < paid_places {
+                let prize = (tournament.prize_pool as u128)
+                    .checked_mul(tournament.payout_table_bps[rank] as u128)
+                    .ok_or(BetError::PriceCalculationOverflow)?
+                    / BPS_DENOMINATOR as u128;
+                entry.prize = prize as u64;
+                distributed = distributed
+                    .checked_add(entry.prize)
+                    .ok_or(BetError::PriceCalculationOverflow)?;
+            }
+        }
+        // Rounding dust and unfilled places go to the winner.
+        if let Some((_, winner)) = entries.first_mut() {
+            winner.prize = winner
+                .prize
+                .checked_add(tournament.prize_pool.saturating_sub(distributed))
+                .ok_or(BetError::PriceCalculationOverflow)?;
+        }
+
+        for (i, entry) in entries.iter() {
+            let mut data = entry_accounts[*i].try_borrow_mut_data()?;
+            entry.try_serialize(&mut &mut data[..])?;
+        }
+        tournament.settled = true;
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct ClaimTournamentPrize<'info> {
+    #[account(mut)]
+    pub user_signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [PROFILE, user_signer.key().as_ref()],
+        bump = user_profile.bump,
+        constraint = user_profile.authority == user_signer.key() @ BetError::UserProfileAuthorityMismatch
+    )]
+    pub user_profile: Account<'info, UserProfile>,
+    #[account(
+        seeds = [TOURNAMENT, &tournament.tournament_id.to_le_bytes()],
+        bump = tournament.bump
+    )]
+    pub tournament: Account<'info, Tournament>,
+    #[account(
+        mut,
+        seeds = [TOURNAMENT_ENTRY, tournament.key().as_ref(), user_signer.key().as_ref()],
+        bump = tournament_entry.bump
+    )]
+    pub tournament_entry: Account<'info, TournamentEntry>,
+}
+
+impl<'info> ClaimTournamentPrize<'info> {
+    pub fn claim_tournament_prize(&mut self) -> Result<()> {
+        let entry = &mut self.tournament_entry;
+        let user_profile = &mut self.user_profile;
+
+        require!(self.tournament.settled, BetError::TournamentNotSettled);
+        require!(entry.prize > 0 && !entry.prize_claimed, BetError::NoPrizeToClaim);
+
+        user_profile.points = user_profile
+            .points
+            .checked_add(entry.prize)
+            .ok_or(BetError::PriceCalculationOverflow)?;
+        entry.prize_claimed = true;
+
+        msg!("Tournament prize of {} points claimed (rank {}).", entry.prize, entry.rank);
+        Ok(())
+    }
+}
@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{CONFIG, PROFILE, TRANSFER_WINDOW_SECONDS};
+use crate::errors::BetError;
+use crate::events::PointsTransferred;
+use crate::state::{ProgramConfig, UserProfile};
+
+// Gifts points between profiles. Works on the base layer, or on the ephemeral rollup
+// when both profiles are delegated.
+#[derive(Accounts)]
+#[instruction(recipient: Pubkey)]
+pub struct TransferPoints<'info> {
+    pub user_signer: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [PROFILE, user_signer.key().as_ref()],
+        bump = sender_profile.bump,
+        constraint = sender_profile.authority == user_signer.key() @ BetError::UserProfileAuthorityMismatch
+    )]
+    pub sender_profile: Account<'info, UserProfile>,
+    #[account(
+        mut,
+        seeds = [PROFILE, recipient.as_ref()],
+        bump = recipient_profile.bump,
+        constraint = recipient_profile.authority == recipient @ BetError::UserProfileAuthorityMismatch
+    )]
+    pub recipient_profile: Account<'info, UserProfile>,
+    #[account(seeds = [CONFIG], bump = program_config.bump)]
+    pub program_config: Account<'info, ProgramConfig>,
+}
+
+impl<'info> TransferPoints<'info> {
+    pub fn transfer_points(&mut self, recipient: Pubkey, amount: u64) -> Result<()> {
+        let config = &self.program_config;
+        let sender = &mut self.sender_profile;
+        let now = Clock::get()?.unix_timestamp;
+
+        require!(config.points_transfers_enabled, BetError::TransfersDisabled);
+        require!(recipient != sender.authority, BetError::InvalidTransferRecipient);
+        require!(amount > 0, BetError::ZeroAmount);
+        require!(sender.points >= amount, BetError::InsufficientPoints);
+
+        let window_end = sender
+            .transfer_window_start
+            .checked_add(TRANSFER_WINDOW_SECONDS)
+            .ok_or(BetError::TimestampOverflow)?;
+        if now >= window_end {
+            sender.transfer_window_start = now;
+            sender.transferred_in_window = 0;
+        }
+        let transferred = sender
+            .transferred_in_window
+            .checked_add(amount)
+            .ok_or(BetError::PriceCalculationOverflow)?;
+        require!(
+            config.daily_transfer_cap == 0 || transferred <= config.daily_transfer_cap,
+            BetError::TransferCapExceeded
+        );
+
+        sender.transferred_in_window = transferred;
+        sender.points = sender
+            .points
+            .checked_sub(amount)
+            .ok_or_else(|| error!(BetError::InsufficientPoints))?;
+        let recipient_profile = &mut self.recipient_profile;
+        recipient_profile.points = recipient_profile
+            .points
+            .checked_add(amount)
+            .ok_or(BetError::PriceCalculationOverflow)?;
+
+        emit!(PointsTransferred {
+            from: sender.authority,
+            to: recipient,
+            amount,
+            timestamp: now,
+        });
+        Ok(())
+    }
+}
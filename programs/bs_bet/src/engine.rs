@@ -0,0 +1,721 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::*;
+use crate::errors::BetError;
+use crate::pricing;
+use crate::state::{ActiveBet, BetTerms};
+
+// Bet lifecycle shared by the base-layer and ephemeral instruction families. Everything
+// here works on plain values: no accounts, sysvars or CPIs, so it runs off-chain as-is.
+// Callers read the oracle, then apply the returned points movement to the profile.
+
+pub fn add_price_sample(bet: &mut ActiveBet, price: u64, timestamp: i64) -> Result<()> {
+    bet.sample_price_sum = bet
+        .sample_price_sum
+        .checked_add(price as u128)
+        .ok_or(BetError::PriceCalculationOverflow)?;
+    bet.sample_count = bet
+        .sample_count
+        .checked_add(1)
+        .ok_or(BetError::PriceCalculationOverflow)?;
+    bet.last_sample_timestamp = timestamp;
+    Ok(())
+}
+
+pub fn average_sample_price(bet: &ActiveBet) -> Result<u64> {
+    require!(bet.sample_count > 0, BetError::PythPriceTooOldOrUnavailable);
+    let average = bet.sample_price_sum / bet.sample_count as u128;
+    u64::try_from(average).map_err(|_| error!(BetError::PriceCalculationOverflow))
+}
+
+// Asian bets sample on fixed slots of `PRICE_SAMPLE_INTERVAL_SECONDS` counted from
+// `opened_at`; the entry print fills slot 0. Each sample must be published inside the
+// next unfilled slot, so a keeper can neither skip slots nor pick a print from elsewhere.
+pub fn record_asian_sample(bet: &mut ActiveBet, price: u64, publish_time: i64) -> Result<()> {
+    require!(
+        publish_time >= bet.opened_at && publish_time < bet.expiry_timestamp,
+        BetError::PriceSampleOutOfSlot
+    );
+    let slot = (publish_time - bet.opened_at) / PRICE_SAMPLE_INTERVAL_SECONDS;
+    require!(slot == bet.sample_count as i64, BetError::PriceSampleOutOfSlot);
+    add_price_sample(bet, price, publish_time)
+}
+
+// Slots that close by expiry; an Asian bet missing any of them is void.
+pub fn required_asian_samples(bet: &ActiveBet) -> u32 {
+    let duration = bet.expiry_timestamp.saturating_sub(bet.opened_at).max(0);
+    u32::try_from(duration / PRICE_SAMPLE_INTERVAL_SECONDS).unwrap_or(u32::MAX)
+}
+
+// Everything a user chooses when opening a bet.
+pub struct BetOrder {
+    pub asset_name: String,
+    pub direction: u8,
+    pub amount: u64,
+    pub duration_seconds: i64,
+    pub terms: BetTerms,
+}
+
+pub fn validate_bet_order(order: &BetOrder) -> Result<()> {
+    require!(
+        SUPPORTED_ASSETS.iter().any(|(name, _)| *name == order.asset_name),
+        BetError::UnsupportedAsset
+    );
+    require!(order.direction == 0 || order.direction == 1, BetError::InvalidDirection);
+    require!(order.amount > 0, BetError::ZeroAmount);
+    require!(order.duration_seconds > 0, BetError::InvalidDuration);
+    if order.terms.bet_type == BET_TYPE_ASIAN {
+        require!(
+            order.duration_seconds >= PRICE_SAMPLE_INTERVAL_SECONDS * MIN_ASIAN_SAMPLE_COUNT as i64,
+            BetError::InvalidDuration
+        );
+    }
+    validate_bet_terms(&order.terms)
+}
+
+// A bet account may only be (re)opened once its previous position is settled.
+pub fn ensure_no_live_bet(bet: &ActiveBet) -> Result<()> {
+    require!(
+        bet.user == Pubkey::default() || bet.status != 0,
+        BetError::BetStillActive
+    );
+    Ok(())
+}
+
+/// Points movement produced by opening a position; applied to the owner's profile by the caller.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct OpenedPosition {
+    pub stake: u64,
+}
+
+/// Outcome of settling a position; `payout` is 0 for a loss and the stake for a void.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SettledPosition {
+    pub won: bool,
+    pub voided: bool,
+    pub payout: u64,
+}
+
+/// Validates `order` and writes a fresh position into `bet`.
+pub fn open_position(
+    bet: &mut ActiveBet,
+    user: Pubkey,
+    order: BetOrder,
+    available_points: u64,
+    initial_price: u64,
+    now: i64,
+) -> Result<OpenedPosition> {
+    validate_bet_order(&order)?;
+    ensure_no_live_bet(bet)?;
+    require!(available_points >= order.amount, BetError::InsufficientPoints);
+
+    bet.user = user;
+    bet.asset_name = order.asset_name;
+    bet.initial_price = initial_price;
+    bet.expiry_timestamp = now
+        .checked_add(order.duration_seconds)
+        .ok_or(BetError::TimestampOverflow)?;
+    bet.direction = order.direction;
+    bet.amount_staked = order.amount;
+    bet.resolved_price = 0;
+    bet.status = 0;
+    bet.opened_at = now;
+    apply_bet_terms(bet, &order.terms, order.duration_seconds, now)?;
+    Ok(OpenedPosition { stake: order.amount })
+}
+
+/// Settles an expired live position against `resolution_price`.
+pub fn settle_position(bet: &mut ActiveBet, resolution_price: u64, now: i64) -> Result<SettledPosition> {
+    require!(bet.status == 0, BetError::BetNotActiveOrAlreadyResolved);
+    require!(now > bet.expiry_timestamp, BetError::BetNotYetExpired);
+
+    // An average with missed slots is not what the bet was priced as; refund it instead,
+    // but only once the next slot is too old for the sampling crank to fill.
+    if bet.bet_type == BET_TYPE_ASIAN && bet.sample_count < required_asian_samples(bet) {
+        let next_slot_closes_at = (bet.sample_count as i64 + 1)
+            .checked_mul(PRICE_SAMPLE_INTERVAL_SECONDS)
+            .and_then(|offset| offset.checked_add(bet.opened_at))
+            .ok_or(BetError::TimestampOverflow)?;
+        require!(
+            now > next_slot_closes_at + MAXIMUM_LIVE_PRICE_AGE_SECONDS as i64,
+            BetError::PriceSampleSlotStillOpen
+        );
+        bet.resolved_price = resolution_price;
+        bet.status = 4;
+        return Ok(SettledPosition { won: false, voided: true, payout: bet.amount_staked });
+    }
+
+    bet.resolved_price = if bet.bet_type == BET_TYPE_ASIAN {
+        add_price_sample(bet, resolution_price, now)?;
+        average_sample_price(bet)?
+    } else {
+        resolution_price
+    };
+
+    if bet_won_at_expiry(bet) {
+        bet.status = 1;
+        Ok(SettledPosition { won: true, voided: false, payout: bet_payout(bet)? })
+    } else {
+        bet.status = 2;
+        Ok(SettledPosition { won: false, voided: false, payout: 0 })
+    }
+}
+
+pub fn validate_bet_terms(terms: &BetTerms) -> Result<()> {
+    if terms.bet_type != BET_TYPE_STANDARD && terms.bet_type != BET_TYPE_ASIAN {
+        require!(terms.strike_offset_bps == 0, BetError::InvalidStrikeOffset);
+    }
+    match terms.bet_type {
+        BET_TYPE_STANDARD | BET_TYPE_ASIAN => {
+            require!(
+                terms.strike_offset_bps.abs() <= MAX_STRIKE_OFFSET_BPS,
+                BetError::InvalidStrikeOffset
+            );
+            Ok(())
+        }
+        BET_TYPE_TOUCH | BET_TYPE_NO_TOUCH => {
+            require!(terms.barrier_price > 0, BetError::InvalidBarrier);
+            Ok(())
+        }
+        BET_TYPE_RANGE => {
+            require!(
+                terms.lower_strike_price > 0 && terms.lower_strike_price < terms.upper_strike_price,
+                BetError::InvalidRange
+            );
+            Ok(())
+        }
+        _ => Err(error!(BetError::InvalidBetType)),
+    }
+}
+
+// Writes the type-specific fields of a freshly opened bet. `bet.initial_price`,
+// `bet.direction` and `bet.expiry_timestamp` must already be set.
+pub fn apply_bet_terms(
+    bet: &mut ActiveBet,
+    terms: &BetTerms,
+    duration_seconds: i64,
+    now: i64,
+) -> Result<()> {
+    bet.bet_type = terms.bet_type;
+    bet.sample_count = 0;
+    bet.sample_price_sum = 0;
+    bet.last_sample_timestamp = 0;
+    bet.barrier_price = 0;
+    bet.lower_strike_price = 0;
+    bet.upper_strike_price = 0;
+    bet.strike_price = bet.initial_price;
+    bet.payout_multiplier_bps = EVEN_MONEY_MULTIPLIER_BPS;
+
+    match terms.bet_type {
+        BET_TYPE_STANDARD | BET_TYPE_ASIAN => {
+            if terms.bet_type == BET_TYPE_ASIAN {
+                // The entry print counts as the first sample of the average.
+                let initial_price = bet.initial_price;
+                add_price_sample(bet, initial_price, now)?;
+            }
+            // At-the-money bets keep even-money odds; offset strikes are priced.
+            if terms.strike_offset_bps != 0 {
+                let strike = (bet.initial_price as i128)
+                    .checked_mul(BPS_DENOMINATOR as i128 + terms.strike_offset_bps as i128)
+                    .ok_or(BetError::PriceCalculationOverflow)?
+                    / BPS_DENOMINATOR as i128;
+                bet.strike_price =
+                    u64::try_from(strike).map_err(|_| error!(BetError::PriceCalculationOverflow))?;
+
+                let volatility_bps = if terms.bet_type == BET_TYPE_ASIAN {
+                    pricing::average_price_volatility_bps(ANNUALIZED_VOLATILITY_BPS)
+                } else {
+                    ANNUALIZED_VOLATILITY_BPS
+                };
+                let below = pricing::probability_below(
+                    bet.initial_price,
+                    bet.strike_price,
+                    duration_seconds,
+                    volatility_bps,
+                );
+                let win_probability = if bet.direction == 1 { 1.0 - below } else { below };
+                bet.payout_multiplier_bps =
+                    pricing::multiplier_bps_from_probability(win_probability)?;
+            }
+        }
+        BET_TYPE_TOUCH | BET_TYPE_NO_TOUCH => {
+            if bet.direction == 1 {
+                require!(terms.barrier_price > bet.initial_price, BetError::InvalidBarrier);
+            } else {
+                require!(terms.barrier_price < bet.initial_price, BetError::InvalidBarrier);
+            }
+            let touch = pricing::touch_probability(
+                bet.initial_price,
+                terms.barrier_price,
+                duration_seconds,
+                ANNUALIZED_VOLATILITY_BPS,
+            );
+            let win_probability = if terms.bet_type == BET_TYPE_TOUCH {
+                touch
+            } else {
+                1.0 - touch
+            };
+            bet.barrier_price = terms.barrier_price;
+            bet.payout_multiplier_bps = pricing::multiplier_bps_from_probability(win_probability)?;
+        }
+        BET_TYPE_RANGE => {
+            let inside = pricing::range_probability(
+                bet.initial_price,
+                terms.lower_strike_price,
+                terms.upper_strike_price,
+                duration_seconds,
+                ANNUALIZED_VOLATILITY_BPS,
+            );
+            let win_probability = if bet.direction == 1 { inside } else { 1.0 - inside };
+            bet.lower_strike_price = terms.lower_strike_price;
+            bet.upper_strike_price = terms.upper_strike_price;
+            bet.payout_multiplier_bps = pricing::multiplier_bps_from_probability(win_probability)?;
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+pub fn barrier_touched(bet: &ActiveBet, price: u64) -> bool {
+    if bet.direction == 1 {
+        price >= bet.barrier_price
+    } else {
+        price <= bet.barrier_price
+    }
+}
+
+// Outcome of a bet that reached expiry with `resolved_price` already set.
+pub fn bet_won_at_expiry(bet: &ActiveBet) -> bool {
+    match bet.bet_type {
+        // Touches normally settle early through the sampling crank; the expiry print is
+        // checked too so an uncranked bet still settles on where the price ended up.
+        BET_TYPE_TOUCH => barrier_touched(bet, bet.resolved_price),
+        BET_TYPE_NO_TOUCH => !barrier_touched(bet, bet.resolved_price),
+        BET_TYPE_RANGE => {
+            let inside = bet.resolved_price >= bet.lower_strike_price
+                && bet.resolved_price <= bet.upper_strike_price;
+            inside == (bet.direction == 1)
+        }
+        _ => {
+            if bet.direction == 1 {
+                bet.resolved_price > bet.strike_price
+            } else {
+                bet.resolved_price < bet.strike_price
+            }
+        }
+    }
+}
+
+// Probability that a live bet still wins, given the current spot and `now`.
+pub fn live_win_probability(bet: &ActiveBet, spot: u64, now: i64) -> Result<f64> {
+    let remaining_seconds = bet
+        .expiry_timestamp
+        .checked_sub(now)
+        .ok_or(BetError::TimestampOverflow)?
+        .max(0);
+    let probability = match bet.bet_type {
+        BET_TYPE_TOUCH | BET_TYPE_NO_TOUCH => {
+            let touch = if barrier_touched(bet, spot) {
+                1.0
+            } else {
+                pricing::touch_probability(
+                    spot,
+                    bet.barrier_price,
+                    remaining_seconds,
+                    ANNUALIZED_VOLATILITY_BPS,
+                )
+            };
+            if bet.bet_type == BET_TYPE_TOUCH {
+                touch
+            } else {
+                1.0 - touch
+            }
+        }
+        BET_TYPE_RANGE => {
+            let inside = pricing::range_probability(
+                spot,
+                bet.lower_strike_price,
+                bet.upper_strike_price,
+                remaining_seconds,
+                ANNUALIZED_VOLATILITY_BPS,
+            );
+            if bet.direction == 1 {
+                inside
+            } else {
+                1.0 - inside
+            }
+        }
+        BET_TYPE_ASIAN => {
+            // The final average blends the samples so far with the rest of the window, which
+            // starts from spot: solve for the level the remaining average has to clear.
+            let window_seconds = bet
+                .expiry_timestamp
+                .checked_sub(bet.opened_at)
+                .ok_or(BetError::TimestampOverflow)?
+                .max(1);
+            let remaining_weight = (remaining_seconds as f64 / window_seconds as f64).clamp(0.0, 1.0);
+            let average = average_sample_price(bet)? as f64;
+            let strike = bet.strike_price as f64;
+            let below = if remaining_weight == 0.0 {
+                if average < strike {
+                    1.0
+                } else {
+                    0.0
+                }
+            } else {
+                let required = (strike - (1.0 - remaining_weight) * average) / remaining_weight;
+                if required <= 0.0 {
+                    0.0
+                } else {
+                    pricing::probability_below(
+                        spot,
+                        required as u64,
+                        remaining_seconds,
+                        pricing::average_price_volatility_bps(ANNUALIZED_VOLATILITY_BPS),
+                    )
+                }
+            };
+            if bet.direction == 1 {
+                1.0 - below
+            } else {
+                below
+            }
+        }
+        _ => {
+            let below = pricing::probability_below(
+                spot,
+                bet.strike_price,
+                remaining_seconds,
+                ANNUALIZED_VOLATILITY_BPS,
+            );
+            if bet.direction == 1 {
+                1.0 - below
+            } else {
+                below
+            }
+        }
+    };
+    Ok(probability.clamp(0.0, 1.0))
+}
+
+pub fn bet_payout(bet: &ActiveBet) -> Result<u64> {
+    let payout = (bet.amount_staked as u128)
+        .checked_mul(bet.payout_multiplier_bps as u128)
+        .ok_or(BetError::PriceCalculationOverflow)?
+        / BPS_DENOMINATOR as u128;
+    u64::try_from(payout).map_err(|_| error!(BetError::PriceCalculationOverflow))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 1_700_000_000;
+    const SPOT: u64 = 150_000_000; // 150.000000
+    // 0.1% of spot, about one standard deviation over a 60 second bet.
+    const STEP: u64 = SPOT / 1_000;
+
+    fn order(bet_type: u8, direction: u8) -> BetOrder {
+        BetOrder {
+            asset_name: "SOL/USD".to_string(),
+            direction,
+            amount: 100,
+            duration_seconds: 60,
+            terms: BetTerms {
+                bet_type,
+                ..BetTerms::default()
+            },
+        }
+    }
+
+    fn opened(order: BetOrder) -> ActiveBet {
+        let mut bet = ActiveBet::default();
+        open_position(&mut bet, Pubkey::new_unique(), order, 1_000, SPOT, NOW).unwrap();
+        bet
+    }
+
+    fn barrier_order(bet_type: u8, direction: u8, barrier_price: u64) -> BetOrder {
+        let mut order = order(bet_type, direction);
+        order.terms.barrier_price = barrier_price;
+        order
+    }
+
+    #[test]
+    fn open_position_writes_an_even_money_standard_bet() {
+        let mut bet = ActiveBet::default();
+        let user = Pubkey::new_unique();
+        let opened = open_position(&mut bet, user, order(BET_TYPE_STANDARD, 1), 1_000, SPOT, NOW).unwrap();
+
+        assert_eq!(opened, OpenedPosition { stake: 100 });
+        assert_eq!(bet.user, user);
+        assert_eq!(bet.status, 0);
+        assert_eq!(bet.opened_at, NOW);
+        assert_eq!(bet.expiry_timestamp, NOW + 60);
+        assert_eq!(bet.strike_price, SPOT);
+        assert_eq!(bet.payout_multiplier_bps, EVEN_MONEY_MULTIPLIER_BPS);
+    }
+
+    #[test]
+    fn open_position_rejects_invalid_orders() {
+        let mut bet = ActiveBet::default();
+        let user = Pubkey::new_unique();
+
+        let mut unsupported = order(BET_TYPE_STANDARD, 1);
+        unsupported.asset_name = "DOGE/USD".to_string();
+        assert_eq!(
+            open_position(&mut bet, user, unsupported, 1_000, SPOT, NOW).unwrap_err(),
+            BetError::UnsupportedAsset.into()
+        );
+        assert_eq!(
+            open_position(&mut bet, user, order(BET_TYPE_STANDARD, 2), 1_000, SPOT, NOW).unwrap_err(),
+            BetError::InvalidDirection.into()
+        );
+        assert_eq!(
+            open_position(&mut bet, user, order(BET_TYPE_STANDARD, 1), 99, SPOT, NOW).unwrap_err(),
+            BetError::InsufficientPoints.into()
+        );
+    }
+
+    #[test]
+    fn open_position_accepts_every_supported_asset() {
+        for (asset_name, _) in SUPPORTED_ASSETS {
+            let mut order = order(BET_TYPE_STANDARD, 1);
+            order.asset_name = asset_name.to_string();
+            assert_eq!(opened(order).asset_name, asset_name);
+        }
+    }
+
+    #[test]
+    fn open_position_refuses_to_overwrite_a_live_bet() {
+        let mut bet = opened(order(BET_TYPE_STANDARD, 1));
+        let user = bet.user;
+        assert_eq!(
+            open_position(&mut bet, user, order(BET_TYPE_STANDARD, 0), 1_000, SPOT, NOW).unwrap_err(),
+            BetError::BetStillActive.into()
+        );
+    }
+
+    #[test]
+    fn settle_position_requires_expiry() {
+        let mut bet = opened(order(BET_TYPE_STANDARD, 1));
+        assert_eq!(
+            settle_position(&mut bet, SPOT, NOW + 60).unwrap_err(),
+            BetError::BetNotYetExpired.into()
+        );
+    }
+
+    #[test]
+    fn settle_position_pays_winners_and_marks_losers() {
+        let mut up = opened(order(BET_TYPE_STANDARD, 1));
+        assert_eq!(
+            settle_position(&mut up, SPOT + 1, NOW + 61).unwrap(),
+            SettledPosition { won: true, voided: false, payout: 200 }
+        );
+        assert_eq!(up.status, 1);
+        assert_eq!(up.resolved_price, SPOT + 1);
+
+        let mut down = opened(order(BET_TYPE_STANDARD, 0));
+        assert_eq!(
+            settle_position(&mut down, SPOT + 1, NOW + 61).unwrap(),
+            SettledPosition { won: false, voided: false, payout: 0 }
+        );
+        assert_eq!(down.status, 2);
+        assert_eq!(
+            settle_position(&mut down, SPOT, NOW + 62).unwrap_err(),
+            BetError::BetNotActiveOrAlreadyResolved.into()
+        );
+    }
+
+    // A three-slot Asian bet: the entry fills slot 0, samples are due in slots 1 and 2.
+    fn opened_asian() -> ActiveBet {
+        let mut order = order(BET_TYPE_ASIAN, 1);
+        order.duration_seconds = PRICE_SAMPLE_INTERVAL_SECONDS * 3;
+        opened(order)
+    }
+
+    #[test]
+    fn settle_position_uses_the_sample_average_for_asian_bets() {
+        let mut bet = opened_asian();
+        record_asian_sample(&mut bet, SPOT + 30, NOW + 5).unwrap();
+        record_asian_sample(&mut bet, SPOT + 30, NOW + 14).unwrap();
+
+        // Average of entry, two samples and the resolution print.
+        let settled = settle_position(&mut bet, SPOT - 20, NOW + 16).unwrap();
+        assert_eq!(bet.resolved_price, SPOT + 10);
+        assert!(settled.won);
+    }
+
+    #[test]
+    fn asian_samples_must_fill_the_next_slot() {
+        let mut bet = opened_asian();
+        for publish_time in [NOW - 1, NOW + 4, NOW + 10, NOW + 15] {
+            assert_eq!(
+                record_asian_sample(&mut bet, SPOT, publish_time).unwrap_err(),
+                BetError::PriceSampleOutOfSlot.into()
+            );
+        }
+        record_asian_sample(&mut bet, SPOT, NOW + 9).unwrap();
+        assert_eq!(
+            record_asian_sample(&mut bet, SPOT, NOW + 9).unwrap_err(),
+            BetError::PriceSampleOutOfSlot.into()
+        );
+        assert_eq!(bet.sample_count, 2);
+    }
+
+    #[test]
+    fn settle_position_voids_asian_bets_with_missed_slots() {
+        let mut bet = opened_asian();
+        record_asian_sample(&mut bet, SPOT + 30, NOW + 5).unwrap();
+
+        // Slot 2 closes at NOW + 15 and can be sampled for another live-price age.
+        let fillable_until = NOW + 15 + MAXIMUM_LIVE_PRICE_AGE_SECONDS as i64;
+        assert_eq!(
+            settle_position(&mut bet, SPOT + 30, fillable_until).unwrap_err(),
+            BetError::PriceSampleSlotStillOpen.into()
+        );
+        assert_eq!(
+            settle_position(&mut bet, SPOT + 30, fillable_until + 1).unwrap(),
+            SettledPosition { won: false, voided: true, payout: 100 }
+        );
+        assert_eq!(bet.status, 4);
+    }
+
+    #[test]
+    fn asian_bets_must_span_the_minimum_slots() {
+        let mut short = order(BET_TYPE_ASIAN, 1);
+        short.duration_seconds = PRICE_SAMPLE_INTERVAL_SECONDS * MIN_ASIAN_SAMPLE_COUNT as i64 - 1;
+        assert_eq!(validate_bet_order(&short).unwrap_err(), BetError::InvalidDuration.into());
+        short.duration_seconds += 1;
+        assert!(validate_bet_order(&short).is_ok());
+    }
+
+    #[test]
+    fn touch_bets_at_expiry_follow_the_resolution_price() {
+        let mut touch = opened(barrier_order(BET_TYPE_TOUCH, 1, SPOT + STEP));
+        touch.resolved_price = SPOT + STEP;
+        assert!(bet_won_at_expiry(&touch));
+        touch.resolved_price = SPOT + STEP - 1;
+        assert!(!bet_won_at_expiry(&touch));
+
+        let mut no_touch = opened(barrier_order(BET_TYPE_NO_TOUCH, 0, SPOT - STEP));
+        no_touch.resolved_price = SPOT - STEP;
+        assert!(!bet_won_at_expiry(&no_touch));
+        no_touch.resolved_price = SPOT - STEP + 1;
+        assert!(bet_won_at_expiry(&no_touch));
+    }
+
+    #[test]
+    fn range_bets_at_expiry_use_inclusive_bounds() {
+        let mut range_order = order(BET_TYPE_RANGE, 1);
+        range_order.terms.lower_strike_price = SPOT - STEP;
+        range_order.terms.upper_strike_price = SPOT + STEP;
+        let mut inside = opened(range_order);
+        let mut outside = inside.clone();
+        outside.direction = 0;
+
+        for (price, inside_wins) in [
+            (SPOT - STEP, true),
+            (SPOT + STEP, true),
+            (SPOT + STEP + 1, false),
+            (SPOT - STEP - 1, false),
+        ] {
+            inside.resolved_price = price;
+            outside.resolved_price = price;
+            assert_eq!(bet_won_at_expiry(&inside), inside_wins);
+            assert_eq!(bet_won_at_expiry(&outside), !inside_wins);
+        }
+    }
+
+    #[test]
+    fn standard_bets_at_expiry_lose_on_an_unchanged_price() {
+        let mut up = opened(order(BET_TYPE_STANDARD, 1));
+        let mut down = opened(order(BET_TYPE_STANDARD, 0));
+        up.resolved_price = SPOT;
+        down.resolved_price = SPOT;
+        assert!(!bet_won_at_expiry(&up));
+        assert!(!bet_won_at_expiry(&down));
+        down.resolved_price = SPOT - 1;
+        assert!(bet_won_at_expiry(&down));
+    }
+
+    #[test]
+    fn bet_payout_applies_the_multiplier() {
+        let mut bet = ActiveBet {
+            amount_staked: 333,
+            payout_multiplier_bps: 25_000,
+            ..ActiveBet::default()
+        };
+        assert_eq!(bet_payout(&bet).unwrap(), 832);
+
+        bet.amount_staked = u64::MAX;
+        assert_eq!(
+            bet_payout(&bet).unwrap_err(),
+            BetError::PriceCalculationOverflow.into()
+        );
+    }
+
+    #[test]
+    fn offset_strikes_pay_more_out_of_the_money() {
+        let mut otm = order(BET_TYPE_STANDARD, 1);
+        otm.terms.strike_offset_bps = 10;
+        let bet = opened(otm);
+        assert_eq!(bet.strike_price, SPOT + STEP);
+        assert!(bet.payout_multiplier_bps > EVEN_MONEY_MULTIPLIER_BPS);
+    }
+
+    #[test]
+    fn validate_bet_terms_edge_cases() {
+        let terms = |bet_type: u8| BetTerms {
+            bet_type,
+            ..BetTerms::default()
+        };
+
+        let mut max_offset = terms(BET_TYPE_ASIAN);
+        max_offset.strike_offset_bps = -MAX_STRIKE_OFFSET_BPS;
+        assert!(validate_bet_terms(&max_offset).is_ok());
+        max_offset.strike_offset_bps = MAX_STRIKE_OFFSET_BPS + 1;
+        assert_eq!(
+            validate_bet_terms(&max_offset).unwrap_err(),
+            BetError::InvalidStrikeOffset.into()
+        );
+
+        let mut offset_touch = terms(BET_TYPE_TOUCH);
+        offset_touch.barrier_price = SPOT;
+        offset_touch.strike_offset_bps = 1;
+        assert_eq!(
+            validate_bet_terms(&offset_touch).unwrap_err(),
+            BetError::InvalidStrikeOffset.into()
+        );
+        assert_eq!(
+            validate_bet_terms(&terms(BET_TYPE_NO_TOUCH)).unwrap_err(),
+            BetError::InvalidBarrier.into()
+        );
+
+        let mut range = terms(BET_TYPE_RANGE);
+        range.lower_strike_price = SPOT;
+        range.upper_strike_price = SPOT;
+        assert_eq!(
+            validate_bet_terms(&range).unwrap_err(),
+            BetError::InvalidRange.into()
+        );
+        range.upper_strike_price = SPOT + 1;
+        assert!(validate_bet_terms(&range).is_ok());
+
+        assert_eq!(
+            validate_bet_terms(&terms(5)).unwrap_err(),
+            BetError::InvalidBetType.into()
+        );
+    }
+
+    #[test]
+    fn asian_cash_out_probability_follows_spot() {
+        let mut bet = opened(order(BET_TYPE_ASIAN, 1));
+        add_price_sample(&mut bet, SPOT, NOW + 30).unwrap();
+
+        let halfway = NOW + 30;
+        let rallied = live_win_probability(&bet, SPOT + SPOT / 10, halfway).unwrap();
+        let crashed = live_win_probability(&bet, SPOT - SPOT / 10, halfway).unwrap();
+        assert!(rallied > 0.99, "rallied: {rallied}");
+        assert!(crashed < 0.01, "crashed: {crashed}");
+    }
+}
@@ -42,4 +42,98 @@ pub enum BetError {
 	DelegatedUseQuickBet,
 	#[msg("Invalid authentication signature or message provided for delegation.")]
 	InvalidDelegationSignature,
+	#[msg("Unknown bet type or operation not supported for this bet type.")]
+	InvalidBetType,
+	#[msg("A bet is still active on this account; resolve it before opening another.")]
+	BetStillActive,
+	#[msg("Bet has already expired.")]
+	BetAlreadyExpired,
+	#[msg("Bet has nothing to cash out at the current price.")]
+	NothingToCashOut,
+	#[msg("Cancellation window has closed or the price has moved since open.")]
+	CancelWindowClosed,
+	#[msg("Signer is not the program config admin.")]
+	UnauthorizedAdmin,
+	#[msg("Program config value is out of range.")]
+	InvalidConfigValue,
+	#[msg("Parlay needs 2-4 legs on distinct supported assets.")]
+	InvalidParlayLegs,
+	#[msg("One price update account must be supplied per parlay leg, in leg order.")]
+	ParlayPriceAccountsMismatch,
+	#[msg("Challenge is not open for this action.")]
+	ChallengeNotOpen,
+	#[msg("Challenge is reserved for a different opponent or cannot be accepted by its creator.")]
+	InvalidChallengeOpponent,
+	#[msg("Tournament parameters are invalid.")]
+	InvalidTournament,
+	#[msg("Tournament is not running.")]
+	TournamentNotRunning,
+	#[msg("Tournament is full.")]
+	TournamentFull,
+	#[msg("Tournament has not ended yet.")]
+	TournamentNotEnded,
+	#[msg("Tournament is already settled.")]
+	TournamentAlreadySettled,
+	#[msg("Tournament is not settled yet.")]
+	TournamentNotSettled,
+	#[msg("Every tournament entry must be supplied exactly once, with no open bets.")]
+	TournamentEntriesMismatch,
+	#[msg("No unclaimed tournament prize for this entry.")]
+	NoPrizeToClaim,
+	#[msg("Daily points already claimed; try again after the cooldown.")]
+	DailyClaimCooldown,
+	#[msg("Daily points faucet is disabled.")]
+	DailyClaimDisabled,
+	#[msg("Invalid referrer.")]
+	InvalidReferrer,
+	#[msg("No referral reward to claim.")]
+	NoReferralReward,
+	#[msg("Invalid achievement definition.")]
+	InvalidAchievement,
+	#[msg("Achievement milestone not reached yet.")]
+	AchievementNotReached,
+	#[msg("Invalid badge token account.")]
+	InvalidBadgeTokenAccount,
+	#[msg("Points transfers are disabled.")]
+	TransfersDisabled,
+	#[msg("Daily points transfer cap exceeded.")]
+	TransferCapExceeded,
+	#[msg("Cannot transfer points to yourself.")]
+	InvalidTransferRecipient,
+	#[msg("Invalid delegation settings.")]
+	InvalidDelegationConfig,
+	#[msg("Delegation time limit reached; disable and re-enable Quick Bets.")]
+	DelegationExpired,
+	#[msg("User is still active; inactivity timeout not reached.")]
+	UserStillActive,
+	#[msg("Delegation state is consistent; nothing to recover.")]
+	NothingToRecover,
+	#[msg("Market round has not finished yet.")]
+	MarketRoundNotFinished,
+	#[msg("Recurring bet plan is paused, cancelled or completed.")]
+	PlanNotActive,
+	#[msg("Recurring bet plan parameters are invalid.")]
+	InvalidPlan,
+	#[msg("Price sample was not published in the bet's next sampling slot.")]
+	PriceSampleOutOfSlot,
+	#[msg("Barrier must be above spot for UP (1) and below spot for DOWN (0).")]
+	InvalidBarrier,
+	#[msg("Range strikes must satisfy 0 < lower < upper.")]
+	InvalidRange,
+	#[msg("Strike offset is out of range or not supported for this bet type.")]
+	InvalidStrikeOffset,
+	#[msg("Computed payout multiplier is outside the allowed range.")]
+	PayoutOutOfRange,
+	#[msg("Settlement requires a verified price published after the bet's expiry.")]
+	SettlementPriceBeforeExpiry,
+	#[msg("Settlement price was published too long after expiry.")]
+	SettlementPriceTooLate,
+	#[msg("Account already has the current layout.")]
+	AccountAlreadyMigrated,
+	#[msg("An Asian sampling slot can still be filled; settle once it has closed.")]
+	PriceSampleSlotStillOpen,
+	#[msg("Expired bet needs a settlement price until the void delay has passed.")]
+	SettlementPriceMissing,
+	#[msg("Bet has not been held long enough to cash out.")]
+	CashOutTooEarly,
 }
@@ -1,18 +1,27 @@
 use anchor_lang::prelude::*;
-// (pyth helper imports live in context impls)
+use anchor_lang::system_program;
+use pyth_solana_receiver_sdk::price_update::{get_feed_id_from_hex, Price, PriceUpdateV2, VerificationLevel};
+use pyth_solana_receiver_sdk::PYTH_PUSH_ORACLE_ID;
 
 // MagicBlock SDK integration
 use ephemeral_rollups_sdk::anchor::ephemeral;
+use ephemeral_rollups_sdk::cpi::DelegateConfig;
 use ephemeral_rollups_sdk::ephem::commit_and_undelegate_accounts;
 
+pub mod badge;
 pub mod constants;
 pub mod context;
+pub mod engine;
 pub mod errors;
+pub mod events;
+pub mod pricing;
 pub mod state;
 
 use crate::constants::*;
 use crate::context::*;
+use crate::engine::BetOrder;
 use crate::errors::BetError;
+use crate::state::{ActiveBet, BetTerms, DelegationArgs, Market, ParlayLegArgs, ProgramConfig, ProgramConfigArgs, TournamentArgs, UserAuthState, UserProfile};
 
 declare_id!("3mhGnRYHNYJ4BMa5P7aGd9DYn3wSwxELNSYqNzRVbtKx"); // YOUR PROGRAM ID
 
@@ -21,6 +30,75 @@ pub fn create_delegation_message(user_pubkey: &Pubkey, nonce: u64) -> String {
     format!("BSBET_DELEGATE_AUTH:{}:{}", user_pubkey, nonce)
 }
 
+// Checks the signed delegation message against the expected content for `nonce`.
+pub(crate) fn verify_delegation_message(
+    user_pubkey: &Pubkey,
+    nonce: u64,
+    user_signed_message: &[u8],
+) -> Result<()> {
+    let expected_message = create_delegation_message(user_pubkey, nonce);
+    if user_signed_message != expected_message.as_bytes() {
+        msg!("Invalid signed message content. Expected for nonce {}.", nonce);
+        return Err(error!(BetError::InvalidDelegationSignature));
+    }
+
+    // --- ED25519 VERIFICATION SKIPPED (Hackathon Compromise) ---
+    msg!("DEMO MODE: Skipping on-chain Ed25519 signature verification.");
+    // --- END OF SKIPPED VERIFICATION ---
+    Ok(())
+}
+
+// Stores the user's delegation overrides on their auth state.
+pub(crate) fn apply_delegation_args(auth_state: &mut UserAuthState, args: &DelegationArgs) -> Result<()> {
+    require!(
+        args.commit_frequency_ms == 0
+            || (MIN_COMMIT_FREQUENCY_MS..=MAX_COMMIT_FREQUENCY_MS).contains(&args.commit_frequency_ms),
+        BetError::InvalidDelegationConfig
+    );
+    require!(args.time_limit_seconds >= 0, BetError::InvalidDelegationConfig);
+    auth_state.commit_frequency_ms = args.commit_frequency_ms;
+    auth_state.validator = args.validator;
+    auth_state.delegation_time_limit_seconds = args.time_limit_seconds;
+    Ok(())
+}
+
+// Effective delegation settings: the user's overrides, else the program defaults.
+pub(crate) fn delegate_config_for(auth_state: &UserAuthState, config: Option<&ProgramConfig>) -> DelegateConfig {
+    let defaults = DelegateConfig::default();
+    DelegateConfig {
+        commit_frequency_ms: if auth_state.commit_frequency_ms > 0 {
+            auth_state.commit_frequency_ms
+        } else {
+            config.map_or(defaults.commit_frequency_ms, |config| config.delegation_commit_frequency_ms)
+        },
+        validator: auth_state
+            .validator
+            .or(config.and_then(|config| config.delegation_validator)),
+    }
+}
+
+// The SDK has no delegation lifetime, so the program records its own. Only new positions
+// are gated on it (`open_bet_ephemeral`); resolving, sampling and committing keep working
+// past the deadline, and keepers undelegate expired users via `undelegate_inactive_user`.
+pub(crate) fn delegation_expiry(auth_state: &UserAuthState, config: Option<&ProgramConfig>, now: i64) -> Result<i64> {
+    let max_delegation_seconds = config.map_or(0, |config| config.max_delegation_seconds);
+    let limit = match (auth_state.delegation_time_limit_seconds, max_delegation_seconds) {
+        (0, max) => max,
+        (requested, 0) => requested,
+        (requested, max) => requested.min(max),
+    };
+    if limit == 0 {
+        return Ok(0);
+    }
+    Ok(now.checked_add(limit).ok_or(BetError::TimestampOverflow)?)
+}
+
+pub(crate) fn read_user_auth_state(account: &AccountInfo) -> Result<UserAuthState> {
+    let data = account.try_borrow_data()?;
+    UserAuthState::try_deserialize(&mut &data[..])
+        .map_err(|_| error!(BetError::NotAuthenticatedOrDelegated))
+}
+
 pub(crate) fn scale_pyth_price_to_6_decimals(price: i64, exponent: i32) -> Result<u64> {
     require!(price >= 0, BetError::NegativePythPrice);
     let mut value: u64 = price as u64;
@@ -45,26 +123,363 @@ pub(crate) fn scale_pyth_price_to_6_decimals(price: i64, exponent: i32) -> Resul
     Ok(value)
 }
 
+pub(crate) fn feed_id_hex_for_asset(asset_name: &str) -> Result<&'static str> {
+    SUPPORTED_ASSETS
+        .iter()
+        .find(|(name, _)| *name == asset_name)
+        .map(|(_, feed_id_hex)| *feed_id_hex)
+        .ok_or_else(|| error!(BetError::UnsupportedAsset))
+}
+
+// Reads a fresh price for `asset_name` from `price_feed`, scaled to 6 decimals.
+pub(crate) fn read_oracle_price(
+    price_feed: &PriceUpdateV2,
+    asset_name: &str,
+    clock: &Clock,
+    maximum_age_seconds: u64,
+) -> Result<u64> {
+    let target_feed_id = get_feed_id_from_hex(feed_id_hex_for_asset(asset_name)?)
+        .map_err(|_| BetError::InvalidPythFeedIdFormat)?;
+    let price_struct = price_feed
+        .get_price_no_older_than(clock, maximum_age_seconds, &target_feed_id)
+        .map_err(|_| BetError::PythPriceFeedError)?;
+    scale_pyth_price_to_6_decimals(price_struct.price, price_struct.exponent)
+}
+
+// Reads `asset_name`'s price from a fully verified update, whatever its age.
+pub(crate) fn read_verified_price(price_feed: &PriceUpdateV2, asset_name: &str) -> Result<Price> {
+    require!(
+        price_feed.verification_level == VerificationLevel::Full,
+        BetError::PythPriceFeedError
+    );
+    let target_feed_id = get_feed_id_from_hex(feed_id_hex_for_asset(asset_name)?)
+        .map_err(|_| BetError::InvalidPythFeedIdFormat)?;
+    price_feed
+        .get_price_unchecked(&target_feed_id)
+        .map_err(|_| error!(BetError::PythPriceFeedError))
+}
+
+// Price that settles a position expiring at `expiry_timestamp`. Only prints published
+// in the `SETTLEMENT_WINDOW_SECONDS` after expiry count, so whoever resolves can neither
+// reach back to an earlier print nor wait for a more favourable later one.
+pub(crate) fn read_settlement_price(
+    price_feed: &PriceUpdateV2,
+    asset_name: &str,
+    expiry_timestamp: i64,
+) -> Result<u64> {
+    let price = read_verified_price(price_feed, asset_name)?;
+    require!(
+        price.publish_time > expiry_timestamp,
+        BetError::SettlementPriceBeforeExpiry
+    );
+    require!(
+        price.publish_time
+            <= expiry_timestamp
+                .checked_add(SETTLEMENT_WINDOW_SECONDS)
+                .ok_or(BetError::TimestampOverflow)?,
+        BetError::SettlementPriceTooLate
+    );
+    scale_pyth_price_to_6_decimals(price.price, price.exponent)
+}
+
+// Pyth's sponsored push feed account for `asset_name` (shard 0). Nobody can choose which
+// print it holds: its publish time only moves forward.
+pub fn push_feed_address(asset_name: &str) -> Result<Pubkey> {
+    let feed_id = get_feed_id_from_hex(feed_id_hex_for_asset(asset_name)?)
+        .map_err(|_| BetError::InvalidPythFeedIdFormat)?;
+    Ok(Pubkey::find_program_address(&[&0u16.to_le_bytes(), &feed_id], &PYTH_PUSH_ORACLE_ID).0)
+}
+
+// Reads a fresh price from `asset_name`'s push feed, for early exits where the caller must
+// not pick the print.
+pub(crate) fn read_push_feed_price(
+    price_feed: &Account<PriceUpdateV2>,
+    asset_name: &str,
+    clock: &Clock,
+    maximum_age_seconds: u64,
+) -> Result<Price> {
+    require_keys_eq!(
+        price_feed.key(),
+        push_feed_address(asset_name)?,
+        BetError::PythPriceFeedError
+    );
+    let target_feed_id = get_feed_id_from_hex(feed_id_hex_for_asset(asset_name)?)
+        .map_err(|_| BetError::InvalidPythFeedIdFormat)?;
+    price_feed
+        .get_price_no_older_than(clock, maximum_age_seconds, &target_feed_id)
+        .map_err(|_| error!(BetError::PythPriceFeedError))
+}
+
+// Opens a position through the engine and debits the stake from the owner's profile.
+pub(crate) fn place_bet(
+    bet: &mut ActiveBet,
+    user_profile: &mut UserProfile,
+    user: Pubkey,
+    order: BetOrder,
+    initial_price: u64,
+    now: i64,
+) -> Result<()> {
+    let opened = engine::open_position(bet, user, order, user_profile.points, initial_price, now)?;
+    user_profile.points = user_profile
+        .points
+        .checked_sub(opened.stake)
+        .ok_or_else(|| error!(BetError::InsufficientPoints))?;
+    user_profile.bets_placed = user_profile.bets_placed.saturating_add(1);
+    if user_profile.referrer.is_some() && user_profile.bets_placed <= REFERRAL_BONUS_BET_COUNT {
+        user_profile.unclaimed_referral_stake = user_profile
+            .unclaimed_referral_stake
+            .checked_add(opened.stake)
+            .ok_or(BetError::PriceCalculationOverflow)?;
+    }
+    Ok(())
+}
+
+// Settles an expired position through the engine and credits any payout to the profile.
+pub(crate) fn settle_expired_bet(
+    bet: &mut ActiveBet,
+    user_profile: &mut UserProfile,
+    resolution_price: u64,
+    now: i64,
+) -> Result<()> {
+    let settled = engine::settle_position(bet, resolution_price, now)?;
+    user_profile.points = user_profile
+        .points
+        .checked_add(settled.payout)
+        .ok_or(BetError::PriceCalculationOverflow)?;
+    if settled.voided {
+        msg!("Asian bet voided: too few price samples; {} points refunded.", settled.payout);
+    } else {
+        record_bet_outcome(user_profile, settled.won);
+    }
+    Ok(())
+}
+
+// Settles an expired live bet on a print from its settlement window. Without a price
+// account the stake is refunded (void), but only once `PRICE_OUTAGE_VOID_SECONDS` have
+// passed since expiry: until then anyone can still settle with an in-window print, so
+// a losing bettor cannot void by simply not supplying one.
+pub(crate) fn settle_or_void_expired_bet(
+    bet: &mut ActiveBet,
+    user_profile: &mut UserProfile,
+    price_feed: Option<&PriceUpdateV2>,
+    clock: &Clock,
+) -> Result<()> {
+    require!(bet.status == 0, BetError::BetNotActiveOrAlreadyResolved);
+    require!(
+        clock.unix_timestamp > bet.expiry_timestamp,
+        BetError::BetNotYetExpired
+    );
+
+    if let Some(price_feed) = price_feed {
+        let resolution_price =
+            read_settlement_price(price_feed, &bet.asset_name, bet.expiry_timestamp)?;
+        return settle_expired_bet(bet, user_profile, resolution_price, clock.unix_timestamp);
+    }
+
+    let void_after = bet
+        .expiry_timestamp
+        .checked_add(PRICE_OUTAGE_VOID_SECONDS)
+        .ok_or(BetError::TimestampOverflow)?;
+    require!(clock.unix_timestamp > void_after, BetError::SettlementPriceMissing);
+
+    user_profile.points = user_profile
+        .points
+        .checked_add(bet.amount_staked)
+        .ok_or(BetError::PriceCalculationOverflow)?;
+    bet.status = 4; // void
+    msg!("Expired bet voided: never settled; {} points refunded.", bet.amount_staked);
+    Ok(())
+}
+
+// Called before accounts leave the rollup. Bets that have not expired yet are left
+// running; expired ones are settled or voided as in `settle_or_void_expired_bet`.
+pub(crate) fn settle_before_undelegation(
+    bet: &mut ActiveBet,
+    user_profile: &mut UserProfile,
+    price_feed: Option<&PriceUpdateV2>,
+    clock: &Clock,
+) -> Result<()> {
+    if bet.status != 0 || clock.unix_timestamp <= bet.expiry_timestamp {
+        return Ok(());
+    }
+    settle_or_void_expired_bet(bet, user_profile, price_feed, clock)
+}
+
+// Refunds a live bet inside the configured cancel window (0 disables cancelling). The
+// price comes from the push feed and must be a print published after the bet opened, so
+// the "price has not moved" check cannot be satisfied with an earlier or hand-picked print.
+pub(crate) fn cancel_live_bet(
+    bet: &mut ActiveBet,
+    user_profile: &mut UserProfile,
+    price_feed: &Account<PriceUpdateV2>,
+    cancel_window_seconds: i64,
+    clock: &Clock,
+) -> Result<()> {
+    require!(bet.status == 0, BetError::BetNotActiveOrAlreadyResolved);
+    require!(cancel_window_seconds > 0, BetError::CancelWindowClosed);
+    let window_closes_at = bet
+        .opened_at
+        .checked_add(cancel_window_seconds)
+        .ok_or(BetError::TimestampOverflow)?;
+    require!(
+        clock.unix_timestamp <= window_closes_at,
+        BetError::CancelWindowClosed
+    );
+
+    let price = read_push_feed_price(
+        price_feed,
+        &bet.asset_name,
+        clock,
+        MAXIMUM_LIVE_PRICE_AGE_SECONDS,
+    )?;
+    require!(
+        price.publish_time > bet.opened_at,
+        BetError::PythPriceTooOldOrUnavailable
+    );
+    let current_price = scale_pyth_price_to_6_decimals(price.price, price.exponent)?;
+    require!(current_price == bet.initial_price, BetError::CancelWindowClosed);
+
+    user_profile.points = user_profile
+        .points
+        .checked_add(bet.amount_staked)
+        .ok_or(BetError::PriceCalculationOverflow)?;
+    bet.resolved_price = current_price;
+    bet.status = 3; // cancelled
+
+    msg!("Bet cancelled within grace window; {} points refunded.", bet.amount_staked);
+    Ok(())
+}
+
+// Grows a program-owned account written with an older, shorter layout to `new_len`, with
+// `payer` topping up the rent. Layouts only ever append fields, so the old bytes keep
+// their meaning and the added ones read as zero; callers fix up any field whose zero
+// value is not the right default. Base layer only: a delegated account is not owned by
+// this program and is rejected.
+pub(crate) fn grow_legacy_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    discriminator: &[u8],
+    new_len: usize,
+) -> Result<()> {
+    require_keys_eq!(*account.owner, crate::ID, ErrorCode::AccountOwnedByWrongProgram);
+    require!(
+        account.try_borrow_data()?.starts_with(discriminator),
+        ErrorCode::AccountDiscriminatorMismatch
+    );
+    require!(account.data_len() < new_len, BetError::AccountAlreadyMigrated);
+
+    let rent_due = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(account.lamports());
+    if rent_due > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.clone(),
+                system_program::Transfer {
+                    from: payer.clone(),
+                    to: account.clone(),
+                },
+            ),
+            rent_due,
+        )?;
+    }
+    account.realloc(new_len, true)?;
+    Ok(())
+}
+
+// `UserAuthState` layouts before `delegation_status` kept an `is_delegated` bool in the
+// same byte; its `true` means delegated, not the pending status that 1 now stands for.
+pub(crate) fn delegation_status_from_legacy_flag(is_delegated: u8) -> u8 {
+    if is_delegated == 0 {
+        DELEGATION_STATUS_UNDELEGATED
+    } else {
+        DELEGATION_STATUS_DELEGATED
+    }
+}
+
+// Adds a newly opened bet to its market's current round.
+pub(crate) fn record_market_bet(market: &mut Market, direction: u8, amount: u64) -> Result<()> {
+    if direction == 1 {
+        market.round_up_stake = market
+            .round_up_stake
+            .checked_add(amount)
+            .ok_or(BetError::PriceCalculationOverflow)?;
+    } else {
+        market.round_down_stake = market
+            .round_down_stake
+            .checked_add(amount)
+            .ok_or(BetError::PriceCalculationOverflow)?;
+    }
+    market.round_bet_count = market.round_bet_count.saturating_add(1);
+    market.total_volume = market
+        .total_volume
+        .checked_add(amount)
+        .ok_or(BetError::PriceCalculationOverflow)?;
+    Ok(())
+}
+
+// Updates the win counters that achievements are measured against.
+pub(crate) fn record_bet_outcome(user_profile: &mut UserProfile, won: bool) {
+    if won {
+        user_profile.total_wins = user_profile.total_wins.saturating_add(1);
+        user_profile.win_streak = user_profile.win_streak.saturating_add(1);
+        user_profile.best_win_streak = user_profile.best_win_streak.max(user_profile.win_streak);
+    } else {
+        user_profile.win_streak = 0;
+    }
+}
+
 // --- Program Module ---
 #[ephemeral]
 #[program]
 pub mod bs_bet {
     use super::*;
 
-    pub fn create_user_profile(ctx: Context<CreateUserProfile>) -> Result<()> {
+    pub fn create_user_profile(
+        ctx: Context<CreateUserProfile>,
+        referrer: Option<Pubkey>,
+    ) -> Result<()> {
         let user_key = ctx.accounts.user_authority.key();
 
         let user_profile = &mut ctx.accounts.user_profile;
-        if user_profile.authority == Pubkey::default() {
+        let is_new_profile = user_profile.authority == Pubkey::default();
+        // `referral_stats` is created by the constraints above, so it may only be passed
+        // when the referral is actually recorded: on a new profile, by someone else.
+        require!(
+            ctx.accounts.referral_stats.is_some() == referrer.is_some(),
+            BetError::InvalidReferrer
+        );
+        if let Some(referrer_key) = referrer {
+            require!(
+                is_new_profile && referrer_key != user_key,
+                BetError::InvalidReferrer
+            );
+        }
+        if is_new_profile {
             user_profile.authority = user_key;
             user_profile.points = INITIAL_USER_POINTS;
+
+            if let Some(referrer_key) = referrer {
+                let stats = ctx
+                    .accounts
+                    .referral_stats
+                    .as_mut()
+                    .ok_or(BetError::InvalidReferrer)?;
+                if stats.referrer == Pubkey::default() {
+                    stats.referrer = referrer_key;
+                    stats.bump = ctx.bumps.referral_stats.ok_or(BetError::InvalidReferrer)?;
+                }
+                stats.referred_count = stats.referred_count.saturating_add(1);
+                user_profile.referrer = Some(referrer_key);
+            }
         }
         user_profile.bump = ctx.bumps.user_profile;
 
         let auth_state = &mut ctx.accounts.user_auth_state_for_profile_creation;
         if auth_state.user_authority == Pubkey::default() {
             auth_state.user_authority = user_key;
-            auth_state.is_delegated = false;
+            auth_state.delegation_status = DELEGATION_STATUS_UNDELEGATED;
             auth_state.delegation_timestamp = 0;
             auth_state.nonce = 0;
             auth_state.bump = ctx.bumps.user_auth_state_for_profile_creation;
@@ -80,17 +495,81 @@ pub mod bs_bet {
             active_bet.amount_staked = 0;
             active_bet.resolved_price = 0;
             active_bet.status = 2; // treat as not-active until opened
+            active_bet.bet_type = BET_TYPE_STANDARD;
+            active_bet.payout_multiplier_bps = EVEN_MONEY_MULTIPLIER_BPS;
         }
         active_bet.bump = ctx.bumps.active_bet;
 
         msg!(
-            "User initialized. delegated={} profile_points={}",
-            auth_state.is_delegated,
+            "User initialized. delegation_status={} profile_points={}",
+            auth_state.delegation_status,
             user_profile.points
         );
         Ok(())
     }
 
+    pub fn claim_referral_reward(ctx: Context<ClaimReferralReward>, referee: Pubkey) -> Result<()> {
+        ctx.accounts.claim_referral_reward(referee)?;
+        Ok(())
+    }
+
+    pub fn create_achievement(
+        ctx: Context<CreateAchievement>,
+        id: u16,
+        kind: u8,
+        threshold: u64,
+    ) -> Result<()> {
+        ctx.accounts.create_achievement(
+            id,
+            kind,
+            threshold,
+            ctx.bumps.achievement,
+            ctx.bumps.badge_mint,
+        )?;
+        Ok(())
+    }
+
+    pub fn update_achievement(ctx: Context<UpdateAchievement>, threshold: u64) -> Result<()> {
+        ctx.accounts.update_achievement(threshold)?;
+        Ok(())
+    }
+
+    pub fn claim_achievement_badge(ctx: Context<ClaimAchievementBadge>) -> Result<()> {
+        ctx.accounts.claim_achievement_badge(ctx.bumps.achievement_claim)?;
+        Ok(())
+    }
+
+    pub fn transfer_points(
+        ctx: Context<TransferPoints>,
+        recipient: Pubkey,
+        amount: u64,
+    ) -> Result<()> {
+        ctx.accounts.transfer_points(recipient, amount)?;
+        Ok(())
+    }
+
+    pub fn claim_daily_points(ctx: Context<ClaimDailyPoints>) -> Result<()> {
+        ctx.accounts.claim_daily_points()?;
+        Ok(())
+    }
+
+    pub fn initialize_program_config(
+        ctx: Context<InitializeProgramConfig>,
+        args: ProgramConfigArgs,
+    ) -> Result<()> {
+        ctx.accounts
+            .initialize_program_config(args, ctx.bumps.program_config)?;
+        Ok(())
+    }
+
+    pub fn update_program_config(
+        ctx: Context<UpdateProgramConfig>,
+        args: ProgramConfigArgs,
+    ) -> Result<()> {
+        ctx.accounts.update_program_config(args)?;
+        Ok(())
+    }
+
     pub fn manage_delegation(
         ctx: Context<ManageDelegation>,
         delegation_action: u8,
@@ -109,8 +588,8 @@ pub mod bs_bet {
                 return Err(error!(BetError::UserProfileAuthorityMismatch));
             }
             // Client-side should ideally check if already delegated before calling.
-            // If called again while is_delegated = true, it effectively re-verifies for current nonce.
-            if auth_state.is_delegated && auth_state.nonce > 0 {
+            // If called again while pending, it effectively re-verifies for current nonce.
+            if auth_state.delegation_status == DELEGATION_STATUS_PENDING && auth_state.nonce > 0 {
                 // If already delegated and nonce was incremented
                 msg!("Already processed for delegation. If MB SDK call failed, client can retry delegate_auth_state.");
                 // No error, allow client to proceed to delegate_auth_state if needed.
@@ -118,27 +597,20 @@ pub mod bs_bet {
                 // return Err(error!(BetError::AlreadyDelegated));
             }
 
-            let current_nonce = auth_state.nonce;
-            let expected_message = create_delegation_message(&user_key, current_nonce);
-            if user_signed_message != expected_message.as_bytes() {
-                msg!(
-                    "Invalid signed message content. Expected for nonce {}.",
-                    current_nonce
-                );
-                return Err(error!(BetError::InvalidDelegationSignature));
-            }
-
-            // --- ED25519 VERIFICATION SKIPPED (Hackathon Compromise) ---
-            msg!("DEMO MODE: Skipping on-chain Ed25519 signature verification for manage_delegation.");
-            // --- END OF SKIPPED VERIFICATION ---
+            verify_delegation_message(&user_key, auth_state.nonce, &user_signed_message)?;
 
-            auth_state.is_delegated = true; // Set to true: ready for MagicBlock SDK call
+            require!(
+                auth_state.delegation_status != DELEGATION_STATUS_DELEGATED,
+                BetError::AlreadyDelegated
+            );
+            // Pending until `delegate_auth_state` lands; `recover_delegation_state` resets it otherwise.
+            auth_state.delegation_status = DELEGATION_STATUS_PENDING;
             auth_state.delegation_timestamp = clock.unix_timestamp;
             auth_state.nonce = auth_state
                 .nonce
                 .checked_add(1)
                 .ok_or(BetError::TimestampOverflow)?;
-            msg!("UserAuthState ready for MagicBlock SDK (is_delegated=true). Nonce incremented to {}.", auth_state.nonce);
+            msg!("UserAuthState ready for MagicBlock SDK (pending delegation). Nonce incremented to {}.", auth_state.nonce);
         } else if delegation_action == 0 {
             // User intends to undelegate ("Disable Quick Bets")
 
@@ -146,17 +618,46 @@ pub mod bs_bet {
             if auth_state.user_authority != user_key {
                 return Err(error!(BetError::UserProfileAuthorityMismatch));
             }
-            // No need to check !auth_state.is_delegated, we are explicitly setting it.
-            auth_state.is_delegated = false;
+            // No need to check the current status, we are explicitly setting it.
+            auth_state.delegation_status = DELEGATION_STATUS_UNDELEGATED;
             // Optionally reset nonce or timestamp here if desired after full undelegation.
             // auth_state.nonce = 0; // Example
-            msg!("UserAuthState locally marked as not delegated (is_delegated=false).");
+            msg!("UserAuthState locally marked as not delegated.");
         } else {
             return Err(error!(BetError::InvalidDelegationSignature)); // Or InvalidDelegationAction
         }
         Ok(())
     }
 
+    pub fn recover_delegation_state(ctx: Context<RecoverDelegationState>) -> Result<()> {
+        ctx.accounts.recover_delegation_state()?;
+        Ok(())
+    }
+
+    pub fn set_delegation_preferences(
+        ctx: Context<SetDelegationPreferences>,
+        delegation_args: DelegationArgs,
+    ) -> Result<()> {
+        ctx.accounts.set_delegation_preferences(delegation_args)?;
+        Ok(())
+    }
+
+    pub fn enable_quick_bets(
+        ctx: Context<EnableQuickBets>,
+        user_signed_message: Vec<u8>,
+        _signature: [u8; 64],
+        delegation_args: DelegationArgs,
+    ) -> Result<()> {
+        ctx.accounts
+            .enable_quick_bets(user_signed_message, delegation_args)?;
+        Ok(())
+    }
+
+    pub fn disable_quick_bets(ctx: Context<DisableQuickBets>) -> Result<()> {
+        ctx.accounts.disable_quick_bets()?;
+        Ok(())
+    }
+
     pub fn delegate_auth_state(ctx: Context<DelegateAuthState>) -> Result<()> {
         ctx.accounts.delegate_accounts()?;
         Ok(())
@@ -178,12 +679,14 @@ pub mod bs_bet {
         direction_arg: u8,
         amount_arg: u64,
         duration_seconds_arg: i64,
+        bet_terms_arg: BetTerms,
     ) -> Result<()> {
         ctx.accounts.open_bet_normal(
             asset_name_arg,
             direction_arg,
             amount_arg,
             duration_seconds_arg,
+            bet_terms_arg,
             ctx.bumps.active_bet,
         )?;
         Ok(())
@@ -195,6 +698,7 @@ pub mod bs_bet {
         direction_arg: u8,
         amount_arg: u64,
         duration_seconds_arg: i64,
+        bet_terms_arg: BetTerms,
         user_authority_for_pdas: Pubkey,
     ) -> Result<()> {
         ctx.accounts.open_bet_ephemeral(
@@ -202,16 +706,222 @@ pub mod bs_bet {
             direction_arg,
             amount_arg,
             duration_seconds_arg,
+            bet_terms_arg,
             user_authority_for_pdas,
         )?;
         Ok(())
     }
 
-    pub fn resolve_bet_normal(ctx: Context<ResolveBetNormal>) -> Result<()> {
+    pub fn record_price_sample(
+        ctx: Context<RecordPriceSample>,
+        user_authority_for_pdas: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.record_price_sample(user_authority_for_pdas)?;
+        Ok(())
+    }
+
+    pub fn settle_and_reopen_bet(
+        ctx: Context<SettleAndReopenBet>,
+        asset_name_arg: String,
+        direction_arg: u8,
+        amount_arg: u64,
+        duration_seconds_arg: i64,
+        bet_terms_arg: BetTerms,
+    ) -> Result<()> {
+        ctx.accounts.settle_and_reopen_bet(
+            asset_name_arg,
+            direction_arg,
+            amount_arg,
+            duration_seconds_arg,
+            bet_terms_arg,
+        )?;
+        Ok(())
+    }
+
+    pub fn create_recurring_plan(
+        ctx: Context<CreateRecurringPlan>,
+        asset_name_arg: String,
+        direction_arg: u8,
+        amount_arg: u64,
+        duration_seconds_arg: i64,
+        max_iterations_arg: u32,
+        stop_loss_points_arg: u64,
+    ) -> Result<()> {
+        ctx.accounts.create_recurring_plan(
+            asset_name_arg,
+            direction_arg,
+            amount_arg,
+            duration_seconds_arg,
+            max_iterations_arg,
+            stop_loss_points_arg,
+            ctx.bumps.recurring_plan,
+        )?;
+        Ok(())
+    }
+
+    pub fn set_recurring_plan_paused(ctx: Context<ManageRecurringPlan>, paused: bool) -> Result<()> {
+        ctx.accounts.set_recurring_plan_paused(paused)?;
+        Ok(())
+    }
+
+    pub fn cancel_recurring_plan(_ctx: Context<CancelRecurringPlan>) -> Result<()> {
+        // Anchor closes the plan account and refunds its rent to the user.
+        Ok(())
+    }
+
+    pub fn execute_recurring_plan(
+        ctx: Context<ExecuteRecurringPlan>,
+        user_authority_for_pdas: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts
+            .execute_recurring_plan(user_authority_for_pdas)?;
+        Ok(())
+    }
+
+    pub fn open_parlay_bet<'info>(
+        ctx: Context<'_, '_, '_, 'info, OpenParlayBet<'info>>,
+        legs_arg: Vec<ParlayLegArgs>,
+        amount_arg: u64,
+        duration_seconds_arg: i64,
+    ) -> Result<()> {
+        ctx.accounts.open_parlay_bet(
+            legs_arg,
+            amount_arg,
+            duration_seconds_arg,
+            ctx.remaining_accounts,
+            ctx.bumps.parlay_bet,
+        )?;
+        Ok(())
+    }
+
+    pub fn resolve_parlay_bet<'info>(
+        ctx: Context<'_, '_, '_, 'info, ResolveParlayBet<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.resolve_parlay_bet(ctx.remaining_accounts)?;
+        Ok(())
+    }
+
+    pub fn create_challenge(
+        ctx: Context<CreateChallenge>,
+        challenge_id: u64,
+        asset_name_arg: String,
+        direction_arg: u8,
+        stake_arg: u64,
+        duration_seconds_arg: i64,
+        opponent_arg: Option<Pubkey>,
+    ) -> Result<()> {
+        ctx.accounts.create_challenge(
+            challenge_id,
+            asset_name_arg,
+            direction_arg,
+            stake_arg,
+            duration_seconds_arg,
+            opponent_arg,
+            ctx.bumps.challenge,
+        )?;
+        Ok(())
+    }
+
+    pub fn accept_challenge(ctx: Context<AcceptChallenge>) -> Result<()> {
+        ctx.accounts.accept_challenge()?;
+        Ok(())
+    }
+
+    pub fn cancel_challenge(ctx: Context<CancelChallenge>) -> Result<()> {
+        ctx.accounts.cancel_challenge()?;
+        Ok(())
+    }
+
+    pub fn settle_challenge(ctx: Context<SettleChallenge>) -> Result<()> {
+        ctx.accounts.settle_challenge()?;
+        Ok(())
+    }
+
+    pub fn create_tournament(
+        ctx: Context<CreateTournament>,
+        tournament_id: u64,
+        args: TournamentArgs,
+    ) -> Result<()> {
+        ctx.accounts
+            .create_tournament(tournament_id, args, ctx.bumps.tournament)?;
+        Ok(())
+    }
+
+    pub fn join_tournament(ctx: Context<JoinTournament>) -> Result<()> {
+        ctx.accounts.join_tournament(ctx.bumps.tournament_entry)?;
+        Ok(())
+    }
+
+    pub fn open_tournament_bet(
+        ctx: Context<OpenTournamentBet>,
+        direction_arg: u8,
+        amount_arg: u64,
+        duration_seconds_arg: i64,
+    ) -> Result<()> {
+        ctx.accounts
+            .open_tournament_bet(direction_arg, amount_arg, duration_seconds_arg)?;
+        Ok(())
+    }
+
+    pub fn resolve_tournament_bet(ctx: Context<ResolveTournamentBet>) -> Result<()> {
+        ctx.accounts.resolve_tournament_bet()?;
+        Ok(())
+    }
+
+    pub fn settle_tournament<'info>(
+        ctx: Context<'_, '_, '_, 'info, SettleTournament<'info>>,
+    ) -> Result<()> {
+        ctx.accounts.settle_tournament(ctx.remaining_accounts)?;
+        Ok(())
+    }
+
+    pub fn claim_tournament_prize(ctx: Context<ClaimTournamentPrize>) -> Result<()> {
+        ctx.accounts.claim_tournament_prize()?;
+        Ok(())
+    }
+
+    pub fn cancel_bet(ctx: Context<CancelBet>) -> Result<()> {
+        ctx.accounts.cancel_bet()?;
+        Ok(())
+    }
+
+    pub fn cash_out_bet(ctx: Context<CashOutBet>) -> Result<()> {
+        ctx.accounts.cash_out_bet()?;
+        Ok(())
+    }
+
+    pub fn resolve_bet_normal(
+        ctx: Context<ResolveBetNormal>,
+        _user_authority_for_pdas: Pubkey,
+    ) -> Result<()> {
         ctx.accounts.resolve_bet_normal()?;
         Ok(())
     }
 
+    pub fn migrate_active_bet(ctx: Context<MigrateActiveBet>) -> Result<()> {
+        ctx.accounts.migrate_active_bet()?;
+        Ok(())
+    }
+
+    pub fn cancel_bet_ephemeral(
+        ctx: Context<CancelBetEphemeral>,
+        user_authority_for_pdas: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts
+            .cancel_bet_ephemeral(user_authority_for_pdas)?;
+        Ok(())
+    }
+
+    pub fn migrate_user_profile(ctx: Context<MigrateUserProfile>) -> Result<()> {
+        ctx.accounts.migrate_user_profile()?;
+        Ok(())
+    }
+
+    pub fn migrate_user_auth_state(ctx: Context<MigrateUserAuthState>) -> Result<()> {
+        ctx.accounts.migrate_user_auth_state()?;
+        Ok(())
+    }
+
     pub fn resolve_bet_ephemeral(
         ctx: Context<ResolveBetEphemeral>,
         user_authority_for_pdas: Pubkey,
@@ -221,7 +931,47 @@ pub mod bs_bet {
         Ok(())
     }
 
+    pub fn initialize_market(ctx: Context<InitializeMarket>, asset_name: String) -> Result<()> {
+        ctx.accounts
+            .initialize_market(asset_name, ctx.bumps.market)?;
+        Ok(())
+    }
+
+    pub fn advance_market_round(ctx: Context<AdvanceMarketRound>) -> Result<()> {
+        ctx.accounts.advance_market_round()?;
+        Ok(())
+    }
+
+    pub fn delegate_market(ctx: Context<DelegateMarket>, asset_name: String) -> Result<()> {
+        ctx.accounts.delegate_accounts(asset_name)?;
+        Ok(())
+    }
+
+    pub fn commit_market(ctx: Context<CommitMarket>) -> Result<()> {
+        ctx.accounts.commit_market()?;
+        Ok(())
+    }
+
+    pub fn undelegate_market(ctx: Context<UndelegateMarket>) -> Result<()> {
+        ctx.accounts.undelegate_market()?;
+        Ok(())
+    }
+
+    pub fn commit_user_state(ctx: Context<CommitUserState>) -> Result<()> {
+        ctx.accounts.commit_user_state()?;
+        Ok(())
+    }
+
+    pub fn commit_user_state_for(
+        ctx: Context<CommitUserStateFor>,
+        user_authority_for_pdas: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.commit_user_state_for(user_authority_for_pdas)?;
+        Ok(())
+    }
+
     pub fn undelegate_from_magicblock(ctx: Context<UndelegateFromMagicBlock>) -> Result<()> {
+        ctx.accounts.settle_expired_bet()?;
         commit_and_undelegate_accounts(
             &ctx.accounts.payer,
             vec![
@@ -234,4 +984,12 @@ pub mod bs_bet {
         )?;
         Ok(())
     }
+
+    pub fn undelegate_inactive_user(
+        ctx: Context<UndelegateInactiveUser>,
+        user_authority_for_pdas: Pubkey,
+    ) -> Result<()> {
+        ctx.accounts.undelegate_inactive_user(user_authority_for_pdas)?;
+        Ok(())
+    }
 }
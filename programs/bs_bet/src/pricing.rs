@@ -0,0 +1,138 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{BPS_DENOMINATOR, MAX_PAYOUT_MULTIPLIER_BPS, SECONDS_PER_YEAR};
+use crate::errors::BetError;
+
+// Driftless lognormal model: fair odds for binary payoffs given spot, barrier/strike,
+// time to expiry and an annualised volatility. Prices use the program's 6-decimal scale.
+
+/// Standard normal CDF (Abramowitz & Stegun 7.1.26, |error| < 1.5e-7).
+fn normal_cdf(x: f64) -> f64 {
+    let z = x.abs() / std::f64::consts::SQRT_2;
+    let t = 1.0 / (1.0 + 0.327_591_1 * z);
+    let poly = t
+        * (0.254_829_592
+            + t * (-0.284_496_736 + t * (1.421_413_741 + t * (-1.453_152_027 + t * 1.061_405_429))));
+    let erf = 1.0 - poly * (-z * z).exp();
+    if x >= 0.0 {
+        0.5 * (1.0 + erf)
+    } else {
+        0.5 * (1.0 - erf)
+    }
+}
+
+/// Volatility scaled to the bet horizon: sigma * sqrt(T).
+fn horizon_volatility(duration_seconds: i64, volatility_bps: u64) -> f64 {
+    let sigma = volatility_bps as f64 / BPS_DENOMINATOR as f64;
+    let years = duration_seconds.max(0) as f64 / SECONDS_PER_YEAR as f64;
+    sigma * years.sqrt()
+}
+
+/// An arithmetic average moves roughly like spot with 1/sqrt(3) of the volatility.
+pub fn average_price_volatility_bps(volatility_bps: u64) -> u64 {
+    (volatility_bps as f64 / 3f64.sqrt()) as u64
+}
+
+/// Probability that the price path touches `barrier` before expiry (reflection principle).
+pub fn touch_probability(spot: u64, barrier: u64, duration_seconds: i64, volatility_bps: u64) -> f64 {
+    if spot == 0 || barrier == 0 {
+        return 0.0;
+    }
+    let vol = horizon_volatility(duration_seconds, volatility_bps);
+    let distance = (barrier as f64 / spot as f64).ln().abs();
+    if distance == 0.0 {
+        return 1.0;
+    }
+    if vol == 0.0 {
+        return 0.0;
+    }
+    (2.0 * normal_cdf(-distance / vol)).min(1.0)
+}
+
+/// Probability that the expiry price finishes below `strike`.
+pub fn probability_below(spot: u64, strike: u64, duration_seconds: i64, volatility_bps: u64) -> f64 {
+    if spot == 0 || strike == 0 {
+        return 0.0;
+    }
+    let vol = horizon_volatility(duration_seconds, volatility_bps);
+    let log_moneyness = (strike as f64 / spot as f64).ln();
+    if vol == 0.0 {
+        return if log_moneyness > 0.0 { 1.0 } else { 0.0 };
+    }
+    // ln(S_T / S) ~ N(-vol^2 / 2, vol^2) for a driftless price.
+    normal_cdf((log_moneyness + 0.5 * vol * vol) / vol)
+}
+
+/// Probability that the expiry price finishes inside `[lower, upper]`.
+pub fn range_probability(
+    spot: u64,
+    lower: u64,
+    upper: u64,
+    duration_seconds: i64,
+    volatility_bps: u64,
+) -> f64 {
+    let below_upper = probability_below(spot, upper, duration_seconds, volatility_bps);
+    let below_lower = probability_below(spot, lower, duration_seconds, volatility_bps);
+    (below_upper - below_lower).clamp(0.0, 1.0)
+}
+
+/// Converts a win probability into a fair payout multiplier (stake included), in bps.
+pub fn multiplier_bps_from_probability(probability: f64) -> Result<u64> {
+    require!(
+        probability.is_finite() && probability > 0.0,
+        BetError::PayoutOutOfRange
+    );
+    let multiplier = (BPS_DENOMINATOR as f64 / probability).floor();
+    require!(
+        multiplier > BPS_DENOMINATOR as f64 && multiplier <= MAX_PAYOUT_MULTIPLIER_BPS as f64,
+        BetError::PayoutOutOfRange
+    );
+    Ok(multiplier as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SPOT: u64 = 150_000_000;
+    const VOLATILITY_BPS: u64 = 8_000;
+
+    #[test]
+    fn normal_cdf_matches_reference_values() {
+        for (x, expected) in [(0.0, 0.5), (1.96, 0.975_002_1), (-1.96, 0.024_997_9)] {
+            assert!((normal_cdf(x) - expected).abs() < 1e-6, "cdf({x}) = {}", normal_cdf(x));
+        }
+    }
+
+    #[test]
+    fn touching_is_at_least_as_likely_as_finishing_beyond_the_barrier() {
+        for duration_seconds in [60, 3_600, 86_400] {
+            for offset_bps in [1u64, 10, 100, 1_000] {
+                let offset = SPOT * offset_bps / BPS_DENOMINATOR;
+                let (upper, lower) = (SPOT + offset, SPOT - offset);
+                let above = 1.0 - probability_below(SPOT, upper, duration_seconds, VOLATILITY_BPS);
+                let below = probability_below(SPOT, lower, duration_seconds, VOLATILITY_BPS);
+                assert!(touch_probability(SPOT, upper, duration_seconds, VOLATILITY_BPS) >= above);
+                assert!(touch_probability(SPOT, lower, duration_seconds, VOLATILITY_BPS) >= below);
+            }
+        }
+    }
+
+    #[test]
+    fn multipliers_round_down_and_stay_in_range() {
+        for (probability, expected) in [
+            (0.5, Some(20_000)),
+            (0.3, Some(33_333)),
+            (0.999_9, Some(10_001)),
+            (0.01, Some(MAX_PAYOUT_MULTIPLIER_BPS)),
+            // Rounds down to even money, which pays nothing on top of the stake.
+            (0.999_95, None),
+            (1.0, None),
+            (0.009_9, None),
+            (0.0, None),
+            (f64::NAN, None),
+        ] {
+            assert_eq!(multiplier_bps_from_probability(probability).ok(), expected, "p = {probability}");
+        }
+    }
+}
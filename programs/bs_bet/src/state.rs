@@ -1,19 +1,33 @@
 use anchor_lang::prelude::*;
 
-use crate::constants::{MAX_ASSET_NAME_LENGTH, STRING_LENGTH_PREFIX};
+use crate::constants::{MAX_ASSET_NAME_LENGTH, MAX_PARLAY_LEGS, MAX_TOURNAMENT_PAYOUT_PLACES, STRING_LENGTH_PREFIX};
 
 #[account]
 #[derive(Default, Debug)]
 pub struct UserAuthState {
 	pub user_authority: Pubkey,
-	pub is_delegated: bool,
+	pub delegation_status: u8, // 0 undelegated, 1 pending delegation, 2 delegated
 	pub delegation_timestamp: i64,
 	pub nonce: u64,
 	pub bump: u8,
+	// Per-user delegation preferences; 0 / None fall back to `ProgramConfig`.
+	pub commit_frequency_ms: u32,
+	pub validator: Option<Pubkey>,
+	pub delegation_time_limit_seconds: i64,
+	pub delegation_expires_at: i64, // 0 = no limit; past it no new quick bets can be opened
+	pub last_activity_timestamp: i64,
 }
 
 // NOTE: Anchor adds the 8-byte discriminator via `space = 8 + ...`.
-pub const USER_AUTH_STATE_SPACE: usize = 32 + 1 + 8 + 8 + 1;
+pub const USER_AUTH_STATE_SPACE: usize = 32 + 1 + 8 + 8 + 1 + 4 + (1 + 32) + 8 + 8 + 8;
+
+/// Per-user overrides for the MagicBlock delegation; zero / `None` use the program defaults.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct DelegationArgs {
+	pub commit_frequency_ms: u32,
+	pub validator: Option<Pubkey>,
+	pub time_limit_seconds: i64,
+}
 
 #[account]
 #[derive(Default, Debug)]
@@ -25,8 +39,22 @@ pub struct ActiveBet {
 	pub direction: u8,
 	pub amount_staked: u64,
 	pub resolved_price: u64,
-	pub status: u8,
+	pub status: u8, // 0 active, 1 won, 2 lost, 3 cancelled, 4 void (refunded)
 	pub bump: u8,
+	pub bet_type: u8,
+	// Asian bets: running sum/count of prices recorded by the sampling crank.
+	pub sample_count: u32,
+	pub sample_price_sum: u128,
+	pub last_sample_timestamp: i64,
+	// Touch / no-touch bets: barrier on the side given by `direction`.
+	pub barrier_price: u64,
+	pub payout_multiplier_bps: u64,
+	// Range bets: inclusive bounds; `direction` 1 = inside wins, 0 = outside wins.
+	pub lower_strike_price: u64,
+	pub upper_strike_price: u64,
+	// Standard / Asian bets: level compared against at expiry (`initial_price` is the entry spot).
+	pub strike_price: u64,
+	pub opened_at: i64,
 }
 
 // NOTE: Anchor adds the 8-byte discriminator via `space = 8 + ...`.
@@ -38,7 +66,30 @@ pub const ACTIVE_BET_SPACE: usize = 32
 	+ 8
 	+ 8
 	+ 1
-	+ 1;
+	+ 1
+	+ 1
+	+ 4
+	+ 16
+	+ 8
+	+ 8
+	+ 8
+	+ 8
+	+ 8
+	+ 8
+	+ 8;
+
+/// Type-specific terms supplied when opening a bet.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct BetTerms {
+	pub bet_type: u8,
+	/// Touch / no-touch only; ignored otherwise.
+	pub barrier_price: u64,
+	/// Range only; ignored otherwise.
+	pub lower_strike_price: u64,
+	pub upper_strike_price: u64,
+	/// Standard / Asian only: strike relative to spot, e.g. 50 = +0.5%, -100 = -1%.
+	pub strike_offset_bps: i32,
+}
 
 #[account]
 #[derive(Default, Debug)]
@@ -46,7 +97,363 @@ pub struct UserProfile {
 	pub authority: Pubkey,
 	pub points: u64,
 	pub bump: u8,
+	pub last_daily_claim_timestamp: i64,
+	pub daily_claim_streak: u16,
+	pub referrer: Option<Pubkey>,
+	pub bets_placed: u32,
+	// Stake from the first `REFERRAL_BONUS_BET_COUNT` bets not yet paid out to the referrer.
+	pub unclaimed_referral_stake: u64,
+	pub total_wins: u32,
+	pub win_streak: u32,
+	pub best_win_streak: u32,
+	pub transfer_window_start: i64,
+	pub transferred_in_window: u64,
+}
+
+// NOTE: Anchor adds the 8-byte discriminator via `space = 8 + ...`.
+pub const USER_PROFILE_SPACE: usize = 32 + 8 + 1 + 8 + 2 + (1 + 32) + 4 + 8 + 4 + 4 + 4 + 8 + 8;
+
+#[account]
+#[derive(Default, Debug)]
+pub struct ReferralStats {
+	pub referrer: Pubkey,
+	pub referred_count: u32,
+	pub earned_rewards: u64,
+	pub bump: u8,
+}
+
+// NOTE: Anchor adds the 8-byte discriminator via `space = 8 + ...`.
+pub const REFERRAL_STATS_SPACE: usize = 32 + 4 + 8 + 1;
+
+#[account]
+#[derive(Default, Debug)]
+pub struct ProgramConfig {
+	pub admin: Pubkey,
+	pub cancel_window_seconds: i64,
+	pub daily_points_amount: u64,
+	pub daily_claim_cooldown_seconds: i64,
+	pub daily_streak_bonus_bps: u16,
+	pub daily_streak_max: u16,
+	pub referral_bonus_bps: u16,
+	pub points_transfers_enabled: bool,
+	pub daily_transfer_cap: u64,
+	pub delegation_commit_frequency_ms: u32,
+	pub delegation_validator: Option<Pubkey>,
+	pub max_delegation_seconds: i64,
+	pub inactivity_timeout_seconds: i64,
+	pub bump: u8,
+}
+
+// NOTE: Anchor adds the 8-byte discriminator via `space = 8 + ...`.
+pub const PROGRAM_CONFIG_SPACE: usize =
+	32 + 8 + 8 + 8 + 2 + 2 + 2 + 1 + 8 + 4 + (1 + 32) + 8 + 8 + 1;
+
+/// Admin-tunable values, shared by `initialize_program_config` and `update_program_config`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ProgramConfigArgs {
+	/// Seconds after open during which an unmoved bet can be cancelled for a full refund; 0 disables.
+	pub cancel_window_seconds: i64,
+	/// Base amount granted by `claim_daily_points`; 0 disables the faucet.
+	pub daily_points_amount: u64,
+	pub daily_claim_cooldown_seconds: i64,
+	/// Extra bps of the base amount per consecutive day after the first, up to `daily_streak_max` days.
+	pub daily_streak_bonus_bps: u16,
+	pub daily_streak_max: u16,
+	/// Share of a referee's stake, over their first bets, paid to the referrer.
+	pub referral_bonus_bps: u16,
+	/// Kill switch for `transfer_points`.
+	pub points_transfers_enabled: bool,
+	/// Most points a profile may send per day; 0 means uncapped.
+	pub daily_transfer_cap: u64,
+	/// How often the ephemeral validator checkpoints delegated accounts to the base layer.
+	pub delegation_commit_frequency_ms: u32,
+	/// Default ephemeral validator; `None` lets the delegation program pick.
+	pub delegation_validator: Option<Pubkey>,
+	/// Upper bound on a delegation's lifetime; 0 means unlimited.
+	pub max_delegation_seconds: i64,
+	/// Idle time after which any keeper may undelegate a user; 0 disables.
+	pub inactivity_timeout_seconds: i64,
+}
+
+#[account]
+#[derive(Default, Debug)]
+pub struct RecurringBetPlan {
+	pub user: Pubkey,
+	pub asset_name: String,
+	pub direction: u8,
+	pub amount_staked: u64,
+	pub duration_seconds: i64,
+	pub max_iterations: u32,
+	pub iterations_executed: u32,
+	// Plan completes instead of opening a bet once points fall to or below this level.
+	pub stop_loss_points: u64,
+	pub status: u8, // 0 active, 1 paused, 2 completed
+	pub last_executed_at: i64,
+	pub bump: u8,
+}
+
+// NOTE: Anchor adds the 8-byte discriminator via `space = 8 + ...`.
+pub const RECURRING_BET_PLAN_SPACE: usize = 32
+	+ (STRING_LENGTH_PREFIX + MAX_ASSET_NAME_LENGTH)
+	+ 1
+	+ 8
+	+ 8
+	+ 4
+	+ 4
+	+ 8
+	+ 1
+	+ 8
+	+ 1;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct ParlayLeg {
+	pub asset_name: String,
+	pub direction: u8,
+	pub initial_price: u64,
+	pub resolved_price: u64,
+}
+
+pub const PARLAY_LEG_SPACE: usize = (STRING_LENGTH_PREFIX + MAX_ASSET_NAME_LENGTH) + 1 + 8 + 8;
+
+/// A leg as chosen by the user when opening a parlay.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ParlayLegArgs {
+	pub asset_name: String,
+	pub direction: u8,
+}
+
+#[account]
+#[derive(Default, Debug)]
+pub struct ParlayBet {
+	pub user: Pubkey,
+	pub legs: Vec<ParlayLeg>,
+	pub amount_staked: u64,
+	pub expiry_timestamp: i64,
+	pub payout_multiplier_bps: u64,
+	pub status: u8, // 0 active, 1 won, 2 lost
+	pub opened_at: i64,
+	pub bump: u8,
+}
+
+// NOTE: Anchor adds the 8-byte discriminator via `space = 8 + ...`.
+pub const PARLAY_BET_SPACE: usize = 32
+	+ (4 + MAX_PARLAY_LEGS * PARLAY_LEG_SPACE)
+	+ 8
+	+ 8
+	+ 8
+	+ 1
+	+ 8
+	+ 1;
+
+#[account]
+#[derive(Default, Debug)]
+pub struct Challenge {
+	pub challenge_id: u64,
+	pub creator: Pubkey,
+	// Pubkey::default() means anyone may accept.
+	pub opponent: Pubkey,
+	pub acceptor: Pubkey,
+	pub asset_name: String,
+	pub creator_direction: u8,
+	pub stake: u64,
+	pub duration_seconds: i64,
+	pub initial_price: u64,
+	pub expiry_timestamp: i64,
+	pub resolved_price: u64,
+	pub status: u8, // 0 open, 1 accepted, 2 settled
+	// Pubkey::default() after settlement means a push (both stakes refunded).
+	pub winner: Pubkey,
+	pub bump: u8,
+}
+
+// NOTE: Anchor adds the 8-byte discriminator via `space = 8 + ...`.
+pub const CHALLENGE_SPACE: usize = 8
+	+ 32
+	+ 32
+	+ 32
+	+ (STRING_LENGTH_PREFIX + MAX_ASSET_NAME_LENGTH)
+	+ 1
+	+ 8
+	+ 8
+	+ 8
+	+ 8
+	+ 8
+	+ 1
+	+ 32
+	+ 1;
+
+#[account]
+#[derive(Default, Debug)]
+pub struct Tournament {
+	pub tournament_id: u64,
+	pub admin: Pubkey,
+	pub start_timestamp: i64,
+	pub end_timestamp: i64,
+	pub entry_fee: u64,
+	pub starting_points: u64,
+	pub max_entrants: u32,
+	pub entrant_count: u32,
+	// Sum of entry fees, paid out by `payout_table_bps` (1st place first).
+	pub prize_pool: u64,
+	pub payout_table_bps: Vec<u16>,
+	pub settled: bool,
+	pub bump: u8,
+}
+
+// NOTE: Anchor adds the 8-byte discriminator via `space = 8 + ...`.
+pub const TOURNAMENT_SPACE: usize = 8
+	+ 32
+	+ 8
+	+ 8
+	+ 8
+	+ 8
+	+ 4
+	+ 4
+	+ 8
+	+ (4 + MAX_TOURNAMENT_PAYOUT_PLACES * 2)
+	+ 1
+	+ 1;
+
+/// Admin-chosen parameters for `create_tournament`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct TournamentArgs {
+	pub start_timestamp: i64,
+	pub end_timestamp: i64,
+	pub entry_fee: u64,
+	pub starting_points: u64,
+	/// At most `MAX_TOURNAMENT_ENTRANTS`.
+	pub max_entrants: u32,
+	pub payout_table_bps: Vec<u16>,
+}
+
+#[account]
+#[derive(Default, Debug)]
+pub struct TournamentEntry {
+	pub tournament: Pubkey,
+	pub user: Pubkey,
+	// Tournament balance, separate from `UserProfile.points`.
+	pub points: u64,
+	pub bet_direction: u8,
+	pub bet_amount: u64,
+	pub bet_initial_price: u64,
+	pub bet_expiry_timestamp: i64,
+	pub bet_status: u8, // 0 active, 1 won, 2 lost / none
+	pub rank: u32,
+	pub prize: u64,
+	pub prize_claimed: bool,
+	pub bump: u8,
 }
 
 // NOTE: Anchor adds the 8-byte discriminator via `space = 8 + ...`.
-pub const USER_PROFILE_SPACE: usize = 32 + 8 + 1;
+pub const TOURNAMENT_ENTRY_SPACE: usize = 32 + 32 + 8 + 1 + 8 + 8 + 8 + 1 + 4 + 8 + 1 + 1;
+
+#[account]
+#[derive(Default, Debug)]
+pub struct Achievement {
+	pub id: u16,
+	pub kind: u8, // 0 total wins, 1 win streak, 2 points balance
+	pub threshold: u64,
+	// Non-transferable Token-2022 mint for the badge, authority = this account.
+	pub mint: Pubkey,
+	pub claimed_count: u32,
+	pub bump: u8,
+	pub mint_bump: u8,
+}
+
+// NOTE: Anchor adds the 8-byte discriminator via `space = 8 + ...`.
+pub const ACHIEVEMENT_SPACE: usize = 2 + 1 + 8 + 32 + 4 + 1 + 1;
+
+// Receipt that a user has minted a given badge.
+#[account]
+#[derive(Default, Debug)]
+pub struct AchievementClaim {
+	pub achievement: Pubkey,
+	pub user: Pubkey,
+	pub claimed_at: i64,
+	pub bump: u8,
+}
+
+// NOTE: Anchor adds the 8-byte discriminator via `space = 8 + ...`.
+pub const ACHIEVEMENT_CLAIM_SPACE: usize = 32 + 32 + 8 + 1;
+
+// Shared per-asset market state. The admin may delegate it to the ephemeral rollup so
+// quick bets can update it at rollup speed. The round stats are best-effort analytics:
+// bettors may leave the market account out, so they undercount and must not drive
+// payouts or risk limits.
+#[account]
+#[derive(Default, Debug)]
+pub struct Market {
+	pub asset_name: String,
+	pub round_id: u64,
+	pub round_started_at: i64,
+	pub round_up_stake: u64,
+	pub round_down_stake: u64,
+	pub round_bet_count: u32,
+	pub total_volume: u64,
+	pub bump: u8,
+}
+
+// NOTE: Anchor adds the 8-byte discriminator via `space = 8 + ...`.
+pub const MARKET_SPACE: usize = (STRING_LENGTH_PREFIX + MAX_ASSET_NAME_LENGTH) + 8 + 8 + 8 + 8 + 4 + 8 + 1;
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::constants::{DELEGATION_STATUS_DELEGATED, DELEGATION_STATUS_UNDELEGATED};
+
+	// What `grow_legacy_account` leaves behind: the old bytes, zero-padded to the new size.
+	fn zero_extended(mut data: Vec<u8>, space: usize) -> Vec<u8> {
+		data.resize(8 + space, 0);
+		data
+	}
+
+	#[test]
+	fn legacy_user_profiles_read_as_fresh_profiles_once_grown() {
+		let authority = Pubkey::new_unique();
+		let mut legacy = UserProfile::DISCRIMINATOR.to_vec();
+		legacy.extend_from_slice(authority.as_ref());
+		legacy.extend_from_slice(&1_234u64.to_le_bytes());
+		legacy.push(254); // bump
+		assert!(UserProfile::try_deserialize(&mut &legacy[..]).is_err());
+
+		let data = zero_extended(legacy, USER_PROFILE_SPACE);
+		let profile = UserProfile::try_deserialize(&mut &data[..]).unwrap();
+		assert_eq!(profile.authority, authority);
+		assert_eq!(profile.points, 1_234);
+		assert_eq!(profile.bump, 254);
+		assert_eq!(profile.referrer, None);
+		assert_eq!(profile.last_daily_claim_timestamp, 0);
+		assert_eq!(profile.bets_placed, 0);
+		assert_eq!(profile.transferred_in_window, 0);
+	}
+
+	#[test]
+	fn legacy_auth_states_keep_their_delegation_flag_once_grown() {
+		let user = Pubkey::new_unique();
+		let mut legacy = UserAuthState::DISCRIMINATOR.to_vec();
+		legacy.extend_from_slice(user.as_ref());
+		legacy.push(1); // is_delegated
+		legacy.extend_from_slice(&1_700_000_000i64.to_le_bytes());
+		legacy.extend_from_slice(&7u64.to_le_bytes());
+		legacy.push(253); // bump
+		assert!(UserAuthState::try_deserialize(&mut &legacy[..]).is_err());
+
+		let data = zero_extended(legacy, USER_AUTH_STATE_SPACE);
+		let auth_state = UserAuthState::try_deserialize(&mut &data[..]).unwrap();
+		assert_eq!(auth_state.user_authority, user);
+		assert_eq!(auth_state.delegation_timestamp, 1_700_000_000);
+		assert_eq!(auth_state.nonce, 7);
+		assert_eq!(auth_state.bump, 253);
+		assert_eq!(auth_state.commit_frequency_ms, 0);
+		assert_eq!(auth_state.validator, None);
+		assert_eq!(auth_state.delegation_expires_at, 0);
+		assert_eq!(
+			crate::delegation_status_from_legacy_flag(auth_state.delegation_status),
+			DELEGATION_STATUS_DELEGATED
+		);
+		assert_eq!(
+			crate::delegation_status_from_legacy_flag(0),
+			DELEGATION_STATUS_UNDELEGATED
+		);
+	}
+}